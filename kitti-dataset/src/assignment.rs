@@ -0,0 +1,101 @@
+//! Linear assignment solver used by the evaluation modules.
+
+/// Solves the rectangular linear assignment problem with the Hungarian
+/// algorithm, minimizing the total cost. It returns the matched
+/// `(row, col)` pairs sorted by row. Every row is matched if there
+/// are no fewer columns than rows, and vice versa.
+pub(crate) fn linear_sum_assignment(cost: &[Vec<f64>]) -> Vec<(usize, usize)> {
+    let num_rows = cost.len();
+    let num_cols = cost.first().map(|row| row.len()).unwrap_or(0);
+    if num_rows == 0 || num_cols == 0 {
+        return vec![];
+    }
+
+    if num_rows > num_cols {
+        let transposed: Vec<Vec<f64>> = (0..num_cols)
+            .map(|col| cost.iter().map(|row| row[col]).collect())
+            .collect();
+        let mut pairs: Vec<_> = solve(&transposed)
+            .into_iter()
+            .map(|(col, row)| (row, col))
+            .collect();
+        pairs.sort_unstable();
+        return pairs;
+    }
+
+    solve(cost)
+}
+
+/// Runs the shortest augmenting path algorithm with potentials. It
+/// requires the number of rows to be no more than the number of
+/// columns.
+fn solve(cost: &[Vec<f64>]) -> Vec<(usize, usize)> {
+    let num_rows = cost.len();
+    let num_cols = cost[0].len();
+
+    // Indices are 1-based and 0 denotes the virtual column.
+    let mut u = vec![0.0; num_rows + 1];
+    let mut v = vec![0.0; num_cols + 1];
+    let mut row_of = vec![0; num_cols + 1];
+    let mut way = vec![0; num_cols + 1];
+
+    for row in 1..=num_rows {
+        row_of[0] = row;
+        let mut col0 = 0;
+        let mut min_v = vec![f64::INFINITY; num_cols + 1];
+        let mut used = vec![false; num_cols + 1];
+
+        loop {
+            used[col0] = true;
+            let row0 = row_of[col0];
+            let mut delta = f64::INFINITY;
+            let mut col1 = 0;
+
+            for col in 1..=num_cols {
+                if used[col] {
+                    continue;
+                }
+                let reduced = cost[row0 - 1][col - 1] - u[row0] - v[col];
+                if reduced < min_v[col] {
+                    min_v[col] = reduced;
+                    way[col] = col0;
+                }
+                if min_v[col] < delta {
+                    delta = min_v[col];
+                    col1 = col;
+                }
+            }
+
+            for col in 0..=num_cols {
+                if used[col] {
+                    u[row_of[col]] += delta;
+                    v[col] -= delta;
+                } else {
+                    min_v[col] -= delta;
+                }
+            }
+
+            col0 = col1;
+            if row_of[col0] == 0 {
+                break;
+            }
+        }
+
+        // Flip the augmenting path.
+        loop {
+            let col1 = way[col0];
+            row_of[col0] = row_of[col1];
+            col0 = col1;
+            if col0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut pairs: Vec<_> = (1..=num_cols)
+        .filter(|&col| row_of[col] != 0)
+        .map(|col| (row_of[col] - 1, col - 1))
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
@@ -33,7 +33,7 @@ macro_rules! ensure {
 }
 
 macro_rules! make_struct {
-    ( $name:ident {$($body:tt)*} ($field:ident : $ty:ident) $($tail:tt)* ) => {
+    ( $name:ident {$($body:tt)*} ($field:ident : $ty:tt) $($tail:tt)* ) => {
         make_struct! {
             $name
             {
@@ -45,7 +45,7 @@ macro_rules! make_struct {
     };
 
     ( $name:ident {$($body:tt)*} ) => {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq)]
         pub struct $name {
             $($body)*
         }
@@ -81,6 +81,43 @@ macro_rules! read_field {
             $($tail)*
         }
     };
+    ( $lines:ident { $($body:tt)* } ($name:expr, $field:ident, [f32; $n:literal]) $($tail:tt)* ) => {
+        read_field! {
+            $lines
+            {
+                $($body)*
+                $field: {
+                    let tokens = next_line(&mut $lines, $name, $n)?;
+                    let value: [f32; $n] = tokens.try_into().unwrap();
+                    value
+                },
+            }
+            $($tail)*
+        }
+    };
+    ( $lines:ident { $($body:tt)* } ($name:expr, $field:ident, f32) $($tail:tt)* ) => {
+        read_field! {
+            $lines
+            {
+                $($body)*
+                $field: {
+                    let tokens = next_line(&mut $lines, $name, 1)?;
+                    tokens[0]
+                },
+            }
+            $($tail)*
+        }
+    };
+    ( $lines:ident { $($body:tt)* } ($name:expr, $field:ident, String) $($tail:tt)* ) => {
+        read_field! {
+            $lines
+            {
+                $($body)*
+                $field: next_calib_line(&mut $lines, $name)?,
+            }
+            $($tail)*
+        }
+    };
     ( $lines:ident { $($body:tt)* } ) => {
         Self {
             $($body)*
@@ -120,13 +157,54 @@ macro_rules! write_field {
             $($tail)*
         }
     };
+    ( $self:ident $f:ident { $($body:tt)* } ($prefix:expr, $field:ident, [f32; $n:literal]) $($tail:tt)* ) => {
+        write_field! {
+            $self
+            $f
+            {
+                $($body)*
+                write_line(
+                    $f,
+                    $prefix,
+                    &[$self.$field],
+                )?;
+            }
+            $($tail)*
+        }
+    };
+    ( $self:ident $f:ident { $($body:tt)* } ($prefix:expr, $field:ident, f32) $($tail:tt)* ) => {
+        write_field! {
+            $self
+            $f
+            {
+                $($body)*
+                write_line(
+                    $f,
+                    $prefix,
+                    &[[$self.$field]],
+                )?;
+            }
+            $($tail)*
+        }
+    };
+    ( $self:ident $f:ident { $($body:tt)* } ($prefix:expr, $field:ident, String) $($tail:tt)* ) => {
+        write_field! {
+            $self
+            $f
+            {
+                $($body)*
+                writeln!($f, "{} {}", $prefix, $self.$field)?;
+            }
+            $($tail)*
+        }
+    };
     ( $self:ident $f:ident { $($body:tt)* } ) => {
         $($body)*
     };
 }
 
 macro_rules! generate_calib_type {
-    ($name:ident; $( ($prefix:expr, $field:ident, $ty:ident) ),*  ) => {
+    ($name:ident; $( ($prefix:expr, $field:ident, $ty:tt) ),*  ) => {
         make_struct! {
             $name
             {}
@@ -216,12 +294,66 @@ generate_calib_type! {
     ("Tr:", tr, ProjectionMatrix)
 }
 
+generate_calib_type! {
+    CamToCamCalibration;
+    ("calib_time:", calib_time, String),
+    ("corner_dist:", corner_dist, f32),
+    ("S_00:", s_00, [f32; 2]),
+    ("K_00:", k_00, Transform2D),
+    ("D_00:", d_00, [f32; 5]),
+    ("R_00:", r_00, Transform2D),
+    ("T_00:", t_00, [f32; 3]),
+    ("S_rect_00:", s_rect_00, [f32; 2]),
+    ("R_rect_00:", r_rect_00, Transform2D),
+    ("P_rect_00:", p_rect_00, ProjectionMatrix),
+    ("S_01:", s_01, [f32; 2]),
+    ("K_01:", k_01, Transform2D),
+    ("D_01:", d_01, [f32; 5]),
+    ("R_01:", r_01, Transform2D),
+    ("T_01:", t_01, [f32; 3]),
+    ("S_rect_01:", s_rect_01, [f32; 2]),
+    ("R_rect_01:", r_rect_01, Transform2D),
+    ("P_rect_01:", p_rect_01, ProjectionMatrix),
+    ("S_02:", s_02, [f32; 2]),
+    ("K_02:", k_02, Transform2D),
+    ("D_02:", d_02, [f32; 5]),
+    ("R_02:", r_02, Transform2D),
+    ("T_02:", t_02, [f32; 3]),
+    ("S_rect_02:", s_rect_02, [f32; 2]),
+    ("R_rect_02:", r_rect_02, Transform2D),
+    ("P_rect_02:", p_rect_02, ProjectionMatrix),
+    ("S_03:", s_03, [f32; 2]),
+    ("K_03:", k_03, Transform2D),
+    ("D_03:", d_03, [f32; 5]),
+    ("R_03:", r_03, Transform2D),
+    ("T_03:", t_03, [f32; 3]),
+    ("S_rect_03:", s_rect_03, [f32; 2]),
+    ("R_rect_03:", r_rect_03, Transform2D),
+    ("P_rect_03:", p_rect_03, ProjectionMatrix)
+}
+
+generate_calib_type! {
+    VeloToCamCalibration;
+    ("calib_time:", calib_time, String),
+    ("R:", r, Transform2D),
+    ("T:", t, [f32; 3]),
+    ("delta_f:", delta_f, [f32; 2]),
+    ("delta_c:", delta_c, [f32; 2])
+}
+
+generate_calib_type! {
+    ImuToVeloCalibration;
+    ("calib_time:", calib_time, String),
+    ("R:", r, Transform2D),
+    ("T:", t, [f32; 3])
+}
+
 struct CalibLine {
     pub name: String,
-    pub values: Vec<f32>,
+    pub text: String,
 }
 
-fn next_line<I>(mut lines: I, expect_name: &str, n_values: usize) -> Result<Vec<f32>, Error>
+fn next_calib_line<I>(mut lines: I, expect_name: &str) -> Result<String, Error>
 where
     I: Iterator<Item = Result<CalibLine, Error>>,
 {
@@ -234,12 +366,29 @@ where
     };
 
     let line = line?;
-    let CalibLine { name, values } = line;
+    let CalibLine { name, text } = line;
     ensure!(
         name == expect_name,
         r#"expect prefix "{expect_name}", but get "{name}""#
     );
 
+    Ok(text)
+}
+
+fn next_line<I>(lines: I, expect_name: &str, n_values: usize) -> Result<Vec<f32>, Error>
+where
+    I: Iterator<Item = Result<CalibLine, Error>>,
+{
+    let text = next_calib_line(lines, expect_name)?;
+    let values: Vec<f32> = text
+        .split_ascii_whitespace()
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| error!(r#"invalid token "{token}" in line "{expect_name} {text}""#))
+        })
+        .try_collect()?;
+
     ensure!(
         values.len() == n_values,
         "expect {n_values} values, but get {} values",
@@ -255,22 +404,15 @@ fn reader_to_lines<R: Read>(reader: R) -> impl Iterator<Item = Result<CalibLine,
         .map(|line| -> Result<_, Error> {
             let line = line?;
             let line = line.trim();
-            let mut tokens = line.split_ascii_whitespace();
 
-            let Some(name) = tokens.next() else {
+            let Some(name) = line.split_ascii_whitespace().next() else {
                 bail!("unexpected empty line");
             };
-            let values: Vec<f32> = tokens
-                .map(|token| {
-                    token
-                        .parse()
-                        .map_err(|_| error!(r#"invalid token "{token}" in line "{line}""#))
-                })
-                .try_collect()?;
+            let text = line[name.len()..].trim_start();
 
             Ok(CalibLine {
                 name: name.to_string(),
-                values,
+                text: text.to_string(),
             })
         })
 }
@@ -297,7 +439,7 @@ fn write_line<const M: usize, const N: usize>(
     name: &str,
     array: &[[f32; N]; M],
 ) -> Result<(), fmt::Error> {
-    write!(f, "{name}:")?;
+    write!(f, "{name}")?;
 
     let slice = array.flat();
     for &val in slice {
@@ -1,6 +1,14 @@
+pub(crate) mod indexed_files;
+mod masked_map;
 mod point_cloud;
+mod projection;
+pub(crate) mod scaled_png;
+mod timestamp;
 
+pub use masked_map::*;
 pub use point_cloud::*;
+pub use projection::*;
+pub use timestamp::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProjectionMatrix(pub [[f32; 4]; 3]);
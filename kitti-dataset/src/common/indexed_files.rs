@@ -0,0 +1,41 @@
+use crate::Error;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Lists the files with the extension in a directory, keyed by the
+/// file stem.
+pub(crate) fn list_files_by_stem(
+    dir: &Path,
+    ext: &str,
+) -> Result<BTreeMap<String, PathBuf>, Error> {
+    let mut map = BTreeMap::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|file_ext| file_ext != ext) || !path.is_file() {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        map.insert(stem.to_string(), path);
+    }
+
+    Ok(map)
+}
+
+/// Lists the files named by digits with the extension in a
+/// directory, such as `000123.txt`, keyed by the parsed index. Other
+/// files are ignored.
+pub(crate) fn list_indexed_files(dir: &Path, ext: &str) -> Result<BTreeMap<usize, PathBuf>, Error> {
+    let map = list_files_by_stem(dir, ext)?
+        .into_iter()
+        .filter(|(stem, _)| stem.bytes().all(|b| b.is_ascii_digit()))
+        .filter_map(|(stem, path)| Some((stem.parse().ok()?, path)))
+        .collect();
+    Ok(map)
+}
@@ -0,0 +1,173 @@
+use crate::Error;
+use image::{DynamicImage, ImageBuffer, ImageFormat, PixelWithColorType};
+use std::{fmt::Debug, marker::PhantomData, path::Path};
+
+/// The 16-bit PNG encoding of a [MaskedMap] kind, such as
+/// [DepthCodec](crate::depth::DepthCodec).
+pub trait MapCodec {
+    /// The value at a pixel.
+    type Value: Copy + Default + PartialEq + Debug;
+
+    /// The pixel type of the PNG image.
+    type Pixel: PixelWithColorType<Subpixel = u16>;
+
+    /// The image format in error messages, e.g. "16-bit grayscale
+    /// depth".
+    const FORMAT: &'static str;
+
+    /// Gets the image if it has the pixel type.
+    fn as_image(image: &DynamicImage) -> Option<&ImageBuffer<Self::Pixel, Vec<u16>>>;
+
+    /// Decodes a pixel. It returns `None` for an invalid pixel.
+    fn decode(pixel: &Self::Pixel) -> Option<Self::Value>;
+
+    /// Encodes a value, or an invalid pixel with `None`.
+    fn encode(value: Option<Self::Value>) -> Self::Pixel;
+
+    /// Checks if the value can be stored. Other values mark the pixel
+    /// invalid.
+    fn is_valid(_value: Self::Value) -> bool {
+        true
+    }
+}
+
+/// A dense map of values with a validity mask, which is stored in
+/// 16-bit PNG files in the KITTI benchmarks.
+///
+/// The kinds are the aliases [DepthMap](crate::depth::DepthMap),
+/// [DisparityMap](crate::stereo::DisparityMap) and
+/// [FlowField](crate::stereo::FlowField).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaskedMap<C>
+where
+    C: MapCodec,
+{
+    width: u32,
+    height: u32,
+    values: Vec<C::Value>,
+    valid: Vec<bool>,
+    _codec: PhantomData<C>,
+}
+
+impl<C> MaskedMap<C>
+where
+    C: MapCodec,
+{
+    /// Creates a map with all pixels invalid.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            values: vec![C::Value::default(); len],
+            valid: vec![false; len],
+            _codec: PhantomData,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the value at the pixel. It returns `None` if the pixel is
+    /// invalid or out of bounds.
+    pub fn get(&self, x: u32, y: u32) -> Option<C::Value> {
+        let idx = self.index(x, y)?;
+        self.valid[idx].then_some(self.values[idx])
+    }
+
+    /// Sets the value at the pixel, or marks it invalid with `None`.
+    /// Values that cannot be stored in the PNG format, such as
+    /// negative depths, also mark the pixel invalid.
+    ///
+    /// # Panics
+    /// It panics if the pixel is out of bounds.
+    pub fn set(&mut self, x: u32, y: u32, value: Option<C::Value>) {
+        let idx = self.index(x, y).expect("pixel out of bounds");
+        let value = value.filter(|&value| C::is_valid(value));
+        self.valid[idx] = value.is_some();
+        self.values[idx] = value.unwrap_or_default();
+    }
+
+    /// Gets the values in row-major order. The values of invalid
+    /// pixels are zero.
+    pub fn values(&self) -> &[C::Value] {
+        &self.values
+    }
+
+    /// Gets the validity mask in row-major order.
+    pub fn valid_mask(&self) -> &[bool] {
+        &self.valid
+    }
+
+    pub fn num_valid(&self) -> usize {
+        self.valid.iter().filter(|&&valid| valid).count()
+    }
+
+    /// Decodes a 16-bit PNG image.
+    pub fn from_image(image: &DynamicImage) -> Result<Self, Error> {
+        let Some(image) = C::as_image(image) else {
+            return Err(Error::InvalidImageFormat(format!(
+                "expect a {} image, but get {:?}",
+                C::FORMAT,
+                image.color()
+            )));
+        };
+
+        let (width, height) = image.dimensions();
+        let (values, valid) = image
+            .pixels()
+            .map(|pixel| match C::decode(pixel) {
+                Some(value) => (value, true),
+                None => (C::Value::default(), false),
+            })
+            .unzip();
+
+        Ok(Self {
+            width,
+            height,
+            values,
+            valid,
+            _codec: PhantomData,
+        })
+    }
+
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let image = image::ImageReader::open(path)?.decode()?;
+        Self::from_image(&image)
+    }
+
+    /// Encodes the map into a 16-bit PNG image as the devkit does.
+    pub fn to_image(&self) -> ImageBuffer<C::Pixel, Vec<u16>> {
+        let mut image = ImageBuffer::new(self.width, self.height);
+        for ((pixel, &value), &valid) in image.pixels_mut().zip(&self.values).zip(&self.valid) {
+            *pixel = C::encode(valid.then_some(value));
+        }
+        image
+    }
+
+    /// Writes the map to a 16-bit PNG file.
+    pub fn write_to_path<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.to_image().save_with_format(path, ImageFormat::Png)?;
+        Ok(())
+    }
+
+    /// Gets the values and the validity mask for in-place updates.
+    pub(crate) fn parts_mut(&mut self) -> (&mut [C::Value], &mut [bool]) {
+        (&mut self.values, &mut self.valid)
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y as usize * self.width as usize + x as usize)
+    }
+}
@@ -1,14 +1,21 @@
 use crate::Error;
+use bytemuck::{Pod, Zeroable};
+use memmap2::Mmap;
 use std::{
     fs::File,
-    io::{prelude::*, BufReader, Cursor},
+    io::{self, prelude::*, BufReader, BufWriter},
+    ops::Deref,
     path::Path,
 };
 
+/// The size of a point record in bytes.
+const POINT_SIZE: usize = 16;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PointCloud(pub Vec<Point>);
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -21,62 +28,71 @@ impl PointCloud {
     where
         R: Read,
     {
-        let try_read_f32 = |reader: &mut R| -> Result<Option<f32>, Error> {
-            let mut bytes = [0u8; 4];
-            let mut buf = &mut bytes[..];
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
 
-            let recv = reader.read(buf)?;
-            if recv == 0 {
-                return Ok(None);
-            }
-            buf = &mut buf[recv..];
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        Self::from_reader(reader)
+    }
 
-            reader.read_exact(buf)?;
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if !bytes.len().is_multiple_of(POINT_SIZE) {
+            return Err(Error::TruncatedPointCloud(bytes.len()));
+        }
 
-            let value = f32::from_le_bytes(bytes);
-            Ok(Some(value))
-        };
+        let points: Vec<Point> = bytes
+            .chunks_exact(POINT_SIZE)
+            .map(|chunk| {
+                let read_f32 = |offset: usize| {
+                    f32::from_le_bytes(chunk[offset..(offset + 4)].try_into().unwrap())
+                };
 
-        let read_f32 = |reader: &mut R| -> Result<f32, Error> {
-            let mut buf = [0u8; 4];
-            reader.read_exact(&mut buf)?;
-            let value = f32::from_le_bytes(buf);
-            Ok(value)
-        };
+                Point {
+                    x: read_f32(0),
+                    y: read_f32(4),
+                    z: read_f32(8),
+                    reflection: read_f32(12),
+                }
+            })
+            .collect();
 
-        let mut points = vec![];
-
-        loop {
-            let Some(x) = try_read_f32(&mut reader)? else {
-                break;
-            };
-            let y = read_f32(&mut reader)?;
-            let z = read_f32(&mut reader)?;
-            let reflection = read_f32(&mut reader)?;
-
-            let point = Point {
-                x,
-                y,
-                z,
-                reflection,
-            };
-            points.push(point)
+        Ok(Self(points))
+    }
+
+    /// Writes the points in Velodyne .bin format, which is a sequence
+    /// of little-endian float32 x, y, z and reflectance values.
+    pub fn write_to_writer<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for point in &self.0 {
+            for value in point.xyzr() {
+                writer.write_all(&value.to_le_bytes())?;
+            }
         }
 
-        Ok(Self(points))
+        writer.flush()?;
+        Ok(())
     }
 
-    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    pub fn write_to_path<P>(&self, path: P) -> io::Result<()>
     where
         P: AsRef<Path>,
     {
-        let reader = BufReader::new(File::open(path)?);
-        Self::from_reader(reader)
+        let writer = BufWriter::new(File::create(path)?);
+        self.write_to_writer(writer)
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
-        let reader = Cursor::new(bytes);
-        Self::from_reader(reader)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.0.len() * POINT_SIZE);
+        self.write_to_writer(&mut buf).unwrap();
+        buf
     }
 }
 
@@ -96,3 +112,91 @@ impl Point {
         [x, y, z, reflection]
     }
 }
+
+/// A read-only point cloud view on a memory-mapped Velodyne .bin
+/// file.
+///
+/// The file content is reinterpreted as a [Point] slice without
+/// copying. If the mapped data cannot be used in place, for example
+/// on big-endian platforms, the points are decoded into an owned
+/// buffer instead.
+///
+/// The file must not be modified by other processes while the view
+/// is alive.
+#[derive(Debug)]
+pub struct PointCloudView {
+    storage: ViewStorage,
+}
+
+#[derive(Debug)]
+enum ViewStorage {
+    Mapped(Mmap),
+    Owned(Vec<Point>),
+}
+
+impl PointCloudView {
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+
+        if !len.is_multiple_of(POINT_SIZE) {
+            return Err(Error::TruncatedPointCloud(len));
+        }
+
+        // Mapping an empty file fails on some platforms.
+        if len == 0 {
+            return Ok(Self {
+                storage: ViewStorage::Owned(vec![]),
+            });
+        }
+
+        // SAFETY: The view is read-only. It is documented that the
+        // file must not be modified while it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let in_place =
+            cfg!(target_endian = "little") && bytemuck::try_cast_slice::<u8, Point>(&mmap).is_ok();
+
+        let storage = if in_place {
+            ViewStorage::Mapped(mmap)
+        } else {
+            let PointCloud(points) = PointCloud::from_bytes(&mmap)?;
+            ViewStorage::Owned(points)
+        };
+
+        Ok(Self { storage })
+    }
+
+    pub fn points(&self) -> &[Point] {
+        match &self.storage {
+            ViewStorage::Mapped(mmap) => bytemuck::cast_slice(mmap),
+            ViewStorage::Owned(points) => points,
+        }
+    }
+
+    /// Gets the points as `[x, y, z, reflection]` arrays.
+    pub fn as_xyzr(&self) -> &[[f32; 4]] {
+        bytemuck::cast_slice(self.points())
+    }
+
+    /// Returns true if the points are read from the mapped memory
+    /// without copying.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.storage, ViewStorage::Mapped(_))
+    }
+
+    pub fn to_point_cloud(&self) -> PointCloud {
+        PointCloud(self.points().to_vec())
+    }
+}
+
+impl Deref for PointCloudView {
+    type Target = [Point];
+
+    fn deref(&self) -> &Self::Target {
+        self.points()
+    }
+}
@@ -0,0 +1,211 @@
+use super::{Point, ProjectionMatrix, Transform2D};
+use crate::calib_codegen::{ObjectCalibration, OdometryCalibration, TrackingCalibration};
+
+/// The camera selector on a KITTI recording platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Camera {
+    /// Camera 0, the left grayscale camera and the reference camera.
+    LeftGray,
+    /// Camera 1, the right grayscale camera.
+    RightGray,
+    /// Camera 2, the left color camera.
+    LeftColor,
+    /// Camera 3, the right color camera.
+    RightColor,
+}
+
+/// The visibility of a projected point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// The point is in front of the camera and inside the image
+    /// bounds. Points in front of the camera are also reported as
+    /// visible if no image size is given.
+    InImage,
+    /// The point is in front of the camera but falls outside the
+    /// image bounds.
+    OutsideImage,
+    /// The point is on or behind the image plane. The pixel
+    /// coordinates of such points are meaningless.
+    BehindCamera,
+}
+
+/// A point projected onto the image plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImagePoint {
+    /// The horizontal pixel coordinate.
+    pub u: f64,
+    /// The vertical pixel coordinate.
+    pub v: f64,
+    /// The distance along the optical axis in meters.
+    pub depth: f64,
+    pub visibility: Visibility,
+}
+
+impl ImagePoint {
+    pub fn is_visible(&self) -> bool {
+        self.visibility == Visibility::InImage
+    }
+}
+
+/// Projects Velodyne points and rectified camera coordinates onto an
+/// image.
+///
+/// The Velodyne points go through the `Tr_velo_to_cam`, `R0_rect`
+/// and `P` chain, while 3D label boxes are already in rectified
+/// camera coordinates and only go through `P`.
+///
+/// ```ignore
+/// let projector = calib.projector(Camera::LeftColor).with_image_size(1242, 375);
+/// let corners = projector.project_corners(&label.corners());
+/// let points = projector.project_point_cloud(&point_cloud.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projector {
+    projection: [[f64; 4]; 3],
+    velo_to_rect: [[f64; 4]; 3],
+    image_size: Option<(u32, u32)>,
+}
+
+impl Projector {
+    /// Creates a projector from the projection matrix of the target
+    /// camera, the rectifying rotation and the Velodyne to camera
+    /// transform.
+    pub fn new(
+        projection: &ProjectionMatrix,
+        r0_rect: &Transform2D,
+        tr_velo_to_cam: &ProjectionMatrix,
+    ) -> Self {
+        let rect = r0_rect.0.map(|row| row.map(f64::from));
+        let tr = tr_velo_to_cam.0.map(|row| row.map(f64::from));
+
+        let mut velo_to_rect = [[0.0; 4]; 3];
+        for (r, row) in velo_to_rect.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| rect[r][k] * tr[k][c]).sum();
+            }
+        }
+
+        Self {
+            projection: projection.0.map(|row| row.map(f64::from)),
+            velo_to_rect,
+            image_size: None,
+        }
+    }
+
+    /// Sets the image size in pixels. Points outside the image are
+    /// marked [Visibility::OutsideImage].
+    pub fn with_image_size(mut self, width: u32, height: u32) -> Self {
+        self.image_size = Some((width, height));
+        self
+    }
+
+    pub fn image_size(&self) -> Option<(u32, u32)> {
+        self.image_size
+    }
+
+    /// Transforms a point from Velodyne coordinates to rectified
+    /// camera coordinates.
+    pub fn velo_to_rect(&self, point: [f64; 3]) -> [f64; 3] {
+        transform(&self.velo_to_rect, point)
+    }
+
+    /// Projects a point in rectified camera coordinates onto the
+    /// image.
+    pub fn rect_to_image(&self, point: [f64; 3]) -> ImagePoint {
+        let [x, y, depth] = transform(&self.projection, point);
+
+        if depth <= 0.0 {
+            return ImagePoint {
+                u: f64::NAN,
+                v: f64::NAN,
+                depth,
+                visibility: Visibility::BehindCamera,
+            };
+        }
+
+        let u = x / depth;
+        let v = y / depth;
+        let visibility = match self.image_size {
+            Some((width, height))
+                if !(0.0..width as f64).contains(&u) || !(0.0..height as f64).contains(&v) =>
+            {
+                Visibility::OutsideImage
+            }
+            _ => Visibility::InImage,
+        };
+
+        ImagePoint {
+            u,
+            v,
+            depth,
+            visibility,
+        }
+    }
+
+    /// Projects a point in Velodyne coordinates onto the image.
+    pub fn velo_to_image(&self, point: [f64; 3]) -> ImagePoint {
+        self.rect_to_image(self.velo_to_rect(point))
+    }
+
+    /// Projects each Velodyne point onto the image. The output has
+    /// the same length and order as the input.
+    pub fn project_point_cloud(&self, points: &[Point]) -> Vec<ImagePoint> {
+        points
+            .iter()
+            .map(|point| {
+                let Point { x, y, z, .. } = *point;
+                self.velo_to_image([x as f64, y as f64, z as f64])
+            })
+            .collect()
+    }
+
+    /// Projects 3D box corners in rectified camera coordinates, such
+    /// as the output of [Label::corners()](crate::object::Label::corners),
+    /// onto the image.
+    pub fn project_corners(&self, corners: &[[f64; 3]; 8]) -> [ImagePoint; 8] {
+        corners.map(|corner| self.rect_to_image(corner))
+    }
+}
+
+impl ObjectCalibration {
+    /// Creates a projector onto the image of the camera.
+    pub fn projector(&self, camera: Camera) -> Projector {
+        let projection = select_camera([&self.p0, &self.p1, &self.p2, &self.p3], camera);
+        Projector::new(projection, &self.r0_rect, &self.tr_velo_to_cam)
+    }
+}
+
+impl TrackingCalibration {
+    /// Creates a projector onto the image of the camera.
+    pub fn projector(&self, camera: Camera) -> Projector {
+        let projection = select_camera([&self.p0, &self.p1, &self.p2, &self.p3], camera);
+        Projector::new(projection, &self.r0_rect, &self.tr_velo_to_cam)
+    }
+}
+
+impl OdometryCalibration {
+    /// Creates a projector onto the image of the camera.
+    ///
+    /// The `Tr` matrix in odometry calibrations maps Velodyne points
+    /// to rectified camera 0 coordinates, so no rectifying rotation is
+    /// applied.
+    pub fn projector(&self, camera: Camera) -> Projector {
+        let projection = select_camera([&self.p0, &self.p1, &self.p2, &self.p3], camera);
+        let identity = Transform2D([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        Projector::new(projection, &identity, &self.tr)
+    }
+}
+
+fn select_camera(matrices: [&ProjectionMatrix; 4], camera: Camera) -> &ProjectionMatrix {
+    let idx = match camera {
+        Camera::LeftGray => 0,
+        Camera::RightGray => 1,
+        Camera::LeftColor => 2,
+        Camera::RightColor => 3,
+    };
+    matrices[idx]
+}
+
+fn transform(matrix: &[[f64; 4]; 3], [x, y, z]: [f64; 3]) -> [f64; 3] {
+    matrix.map(|[a, b, c, d]| a * x + b * y + c * z + d)
+}
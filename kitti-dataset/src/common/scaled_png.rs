@@ -0,0 +1,25 @@
+//! Codec of 16-bit grayscale PNG maps storing `value * scale`, where
+//! zero marks an invalid pixel.
+
+use image::Luma;
+
+pub(crate) fn decode(&Luma([value]): &Luma<u16>, scale: f32) -> Option<f32> {
+    (value != 0).then(|| value as f32 / scale)
+}
+
+/// Encodes a value as the devkit does. Valid values are scaled,
+/// truncated and clamped to `1..=65535`, so that small values remain
+/// valid. Invalid pixels are zero, as are values rejected by
+/// [is_valid].
+pub(crate) fn encode(value: Option<f32>, scale: f32) -> Luma<u16> {
+    match value.filter(|&value| is_valid(value)) {
+        Some(value) => Luma([(value * scale).clamp(1.0, u16::MAX as f32) as u16]),
+        None => Luma([0]),
+    }
+}
+
+/// Checks if a value can be stored. Negative and non-finite values
+/// cannot be represented.
+pub(crate) fn is_valid(value: f32) -> bool {
+    value.is_finite() && value >= 0.0
+}
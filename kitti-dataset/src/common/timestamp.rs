@@ -0,0 +1,217 @@
+use crate::{float_format::format_exp, Error};
+use chrono::NaiveDateTime;
+use itertools::Itertools;
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::{self, prelude::*, BufReader, BufWriter, Cursor},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+const TIMESTAMP_READ_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+const TIMESTAMP_WRITE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.9f";
+
+/// The absolute timestamp used by `timestamps.txt` in raw data
+/// recordings.
+///
+/// ```text
+/// 2011-09-26 13:02:25.964389445
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub NaiveDateTime);
+
+/// The time elapsed since the start of the sequence used by
+/// `times.txt` in odometry sequences.
+///
+/// ```text
+/// 1.036224e-01
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOffset(pub Duration);
+
+impl Timestamp {
+    /// Returns the duration elapsed since an earlier timestamp, or
+    /// `None` if `earlier` is later than `self`.
+    pub fn duration_since(&self, earlier: &Timestamp) -> Option<Duration> {
+        (self.0 - earlier.0).to_std().ok()
+    }
+
+    /// Finds the index of the closest timestamp in a sorted slice.
+    pub fn nearest_index(&self, sorted: &[Timestamp]) -> Option<usize> {
+        nearest_index(self, sorted, |lhs, rhs| {
+            (lhs.0 - rhs.0).abs().to_std().unwrap_or(Duration::MAX)
+        })
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let datetime = NaiveDateTime::parse_from_str(text.trim(), TIMESTAMP_READ_FORMAT)
+            .map_err(|_| Error::InvalidTimestamp(text.to_string()))?;
+        Ok(Self(datetime))
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format(TIMESTAMP_WRITE_FORMAT))
+    }
+}
+
+impl TimeOffset {
+    pub fn as_secs_f64(&self) -> f64 {
+        self.0.as_secs_f64()
+    }
+
+    /// Finds the index of the closest time offset in a sorted slice.
+    pub fn nearest_index(&self, sorted: &[TimeOffset]) -> Option<usize> {
+        nearest_index(self, sorted, |lhs, rhs| lhs.0.abs_diff(rhs.0))
+    }
+}
+
+impl FromStr for TimeOffset {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || Error::InvalidTimestamp(text.to_string());
+
+        let secs: f64 = text.trim().parse().map_err(|_| error())?;
+        if !secs.is_finite() || secs < 0.0 {
+            return Err(error());
+        }
+
+        // Round to nanoseconds to cancel the error from the decimal
+        // to binary conversion.
+        let nanos = (secs * 1e9).round() as u64;
+        Ok(Self(Duration::from_nanos(nanos)))
+    }
+}
+
+impl Display for TimeOffset {
+    /// Writes the offset in seconds in `%.6e` format used by KITTI.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_exp(self.as_secs_f64(), 6))
+    }
+}
+
+macro_rules! impl_line_io {
+    ($ty:ident) => {
+        impl $ty {
+            pub fn iter_from_reader<R>(reader: R) -> impl Iterator<Item = Result<$ty, Error>>
+            where
+                R: Read,
+            {
+                let reader = BufReader::new(reader);
+
+                reader
+                    .lines()
+                    .filter_map(|line| -> Option<Result<$ty, Error>> {
+                        let line = match line {
+                            Ok(line) => line,
+                            Err(err) => return Some(Err(err.into())),
+                        };
+                        let line = line.trim();
+                        if line.is_empty() {
+                            return None;
+                        }
+                        Some(line.parse())
+                    })
+            }
+
+            pub fn iter_from_path<P>(
+                path: P,
+            ) -> Result<impl Iterator<Item = Result<$ty, Error>>, Error>
+            where
+                P: AsRef<Path>,
+            {
+                Ok(Self::iter_from_reader(File::open(path)?))
+            }
+
+            pub fn iter_from_str(text: &str) -> impl Iterator<Item = Result<$ty, Error>> + '_ {
+                let reader = Cursor::new(text);
+                Self::iter_from_reader(reader)
+            }
+
+            pub fn vec_from_reader<R>(reader: R) -> Result<Vec<$ty>, Error>
+            where
+                R: Read,
+            {
+                Self::iter_from_reader(reader).try_collect()
+            }
+
+            pub fn vec_from_path<P>(path: P) -> Result<Vec<$ty>, Error>
+            where
+                P: AsRef<Path>,
+            {
+                Self::iter_from_path(path)?.try_collect()
+            }
+
+            pub fn vec_from_str(text: &str) -> Result<Vec<$ty>, Error> {
+                Self::iter_from_str(text).try_collect()
+            }
+
+            pub fn write_to_writer<W, I, A>(mut writer: W, values: I) -> io::Result<()>
+            where
+                I: IntoIterator<Item = A>,
+                W: Write,
+                A: Borrow<$ty>,
+            {
+                for value in values {
+                    writeln!(writer, "{}", value.borrow())?;
+                }
+
+                writer.flush()?;
+                Ok(())
+            }
+
+            pub fn write_to_path<P, I, A>(path: P, values: I) -> io::Result<()>
+            where
+                I: IntoIterator<Item = A>,
+                P: AsRef<Path>,
+                A: Borrow<$ty>,
+            {
+                let writer = BufWriter::new(File::create(path)?);
+                Self::write_to_writer(writer, values)
+            }
+
+            pub fn write_to_string<I, A>(values: I) -> io::Result<String>
+            where
+                I: IntoIterator<Item = A>,
+                A: Borrow<$ty>,
+            {
+                let mut buf = vec![];
+                Self::write_to_writer(&mut buf, values)?;
+                Ok(String::from_utf8(buf).unwrap())
+            }
+        }
+    };
+}
+
+impl_line_io!(Timestamp);
+impl_line_io!(TimeOffset);
+
+fn nearest_index<T, F>(target: &T, sorted: &[T], distance: F) -> Option<usize>
+where
+    T: Ord,
+    F: Fn(&T, &T) -> Duration,
+{
+    let idx = sorted.partition_point(|value| value < target);
+    let prev = idx.checked_sub(1);
+    let next = (idx < sorted.len()).then_some(idx);
+
+    match (prev, next) {
+        (Some(prev), Some(next)) => {
+            if distance(target, &sorted[prev]) <= distance(target, &sorted[next]) {
+                Some(prev)
+            } else {
+                Some(next)
+            }
+        }
+        (prev, next) => prev.or(next),
+    }
+}
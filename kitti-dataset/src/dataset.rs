@@ -1,11 +1,270 @@
+pub mod depth;
 pub mod object;
+pub mod odometry;
+pub mod raw;
+pub mod stereo;
 pub mod tracking;
 
-use std::{iter, path::Path};
+use crate::{error::KeyMismatch, Error};
+use image::{DynamicImage, ImageReader};
+use itertools::Itertools;
+use std::{
+    collections::BTreeSet,
+    iter,
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
+pub use depth::DepthDataset;
 pub use object::ObjectDataset;
+pub use odometry::OdometryDataset;
+pub use raw::RawDataset;
+pub use stereo::StereoDataset;
 pub use tracking::TrackingDataset;
 
+/// The policy to determine the dataset frames when sub-directories
+/// contain different frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MismatchPolicy {
+    /// Use the frames available in all sub-directories.
+    #[default]
+    Intersection,
+    /// Use the frames available in any sub-directory. Keys missing a
+    /// frame return no sample for that frame.
+    Union,
+    /// Fail with [Error::FrameMismatch] unless all
+    /// sub-directories contain the same frames.
+    Error,
+}
+
+/// The way to find the frames in each sub-directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IndexMode {
+    /// Binary-search the frame count with file existence checks. It
+    /// assumes contiguous frame IDs starting from zero and misses the
+    /// frames after a gap.
+    #[default]
+    Probe,
+    /// List each directory once and parse the frame IDs from file
+    /// names. Sparse IDs are supported, and entries not named after a
+    /// frame are reported as stray files.
+    Scan,
+}
+
+/// Options to open a dataset.
+///
+/// ```ignore
+/// let options = OpenOptions::new()
+///     .with_index_mode(IndexMode::Scan)
+///     .with_mismatch_policy(MismatchPolicy::Union);
+/// let dataset = ObjectDataset::open_with_options("object/training", options)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OpenOptions {
+    mismatch_policy: MismatchPolicy,
+    index_mode: IndexMode,
+}
+
+impl OpenOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mismatch_policy(mut self, policy: MismatchPolicy) -> Self {
+        self.mismatch_policy = policy;
+        self
+    }
+
+    pub fn with_index_mode(mut self, mode: IndexMode) -> Self {
+        self.index_mode = mode;
+        self
+    }
+
+    pub fn mismatch_policy(&self) -> MismatchPolicy {
+        self.mismatch_policy
+    }
+
+    pub fn index_mode(&self) -> IndexMode {
+        self.index_mode
+    }
+}
+
+/// The frame IDs found in a sub-directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirIndex {
+    ids: BTreeSet<usize>,
+    stray_files: Vec<PathBuf>,
+}
+
+impl DirIndex {
+    /// Iterates over frame IDs in ascending order.
+    pub fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ids.iter().copied()
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.ids.contains(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Lists the ranges of missing IDs below the largest ID.
+    pub fn gaps(&self) -> Vec<Range<usize>> {
+        let mut gaps = vec![];
+        let mut next = 0;
+        for &id in &self.ids {
+            if id > next {
+                gaps.push(next..id);
+            }
+            next = id + 1;
+        }
+        gaps
+    }
+
+    /// Gets the entries not named after a frame in sorted order. It is
+    /// always empty in [IndexMode::Probe] mode.
+    pub fn stray_files(&self) -> &[PathBuf] {
+        &self.stray_files
+    }
+
+    /// Indexes a directory of frames named by `width` digits, which
+    /// are files with the extension `ext`, or directories if `ext` is
+    /// `None`.
+    fn open(dir: &Path, width: usize, ext: Option<&str>, mode: IndexMode) -> Result<Self, Error> {
+        let index = match mode {
+            IndexMode::Probe => Self {
+                ids: (0..probe_max_frames(dir, width, ext)).collect(),
+                stray_files: vec![],
+            },
+            IndexMode::Scan => Self::scan(dir, width, ext)?,
+        };
+        Ok(index)
+    }
+
+    fn scan(dir: &Path, width: usize, ext: Option<&str>) -> Result<Self, Error> {
+        let mut ids = BTreeSet::new();
+        let mut stray_files = vec![];
+
+        for entry in dir.read_dir()? {
+            let path = entry?.path();
+            match parse_frame_id(&path, width, ext) {
+                Some(id) => {
+                    ids.insert(id);
+                }
+                None => stray_files.push(path),
+            }
+        }
+        stray_files.sort();
+
+        Ok(Self { ids, stray_files })
+    }
+}
+
+/// Parses the frame ID from a file or directory name. Entries whose
+/// metadata cannot be read, such as dangling symlinks, are not frames.
+fn parse_frame_id(path: &Path, width: usize, ext: Option<&str>) -> Option<usize> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = match ext {
+        Some(ext) => {
+            if !path.is_file() {
+                return None;
+            }
+            file_name.strip_suffix(ext)?.strip_suffix('.')?
+        }
+        None => {
+            if !path.is_dir() {
+                return None;
+            }
+            file_name
+        }
+    };
+
+    if stem.len() != width || !stem.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    stem.parse().ok()
+}
+
+/// Determines the dataset frame IDs from the indices of
+/// sub-directories.
+fn resolve_frame_ids<'a, I>(indices: I, policy: MismatchPolicy) -> Result<Vec<usize>, Error>
+where
+    I: IntoIterator<Item = (&'a str, &'a DirIndex)>,
+{
+    let indices: Vec<_> = indices.into_iter().collect();
+    let Some(((_, first), rest)) = indices.split_first() else {
+        return Ok(vec![]);
+    };
+
+    let ids: Vec<usize> = match policy {
+        MismatchPolicy::Intersection => first
+            .ids()
+            .filter(|&id| rest.iter().all(|(_, index)| index.contains(id)))
+            .collect(),
+        MismatchPolicy::Union => indices
+            .iter()
+            .flat_map(|(_, index)| index.ids())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        MismatchPolicy::Error => {
+            if rest.iter().any(|(_, index)| index.ids != first.ids) {
+                let all_ids: BTreeSet<usize> =
+                    indices.iter().flat_map(|(_, index)| index.ids()).collect();
+                let keys = indices
+                    .iter()
+                    .map(|(key, index)| {
+                        let mut missing = all_ids.difference(&index.ids);
+                        KeyMismatch {
+                            key: key.to_string(),
+                            num_frames: index.len(),
+                            first_missing: missing.next().copied(),
+                            num_missing: all_ids.len() - index.len(),
+                        }
+                    })
+                    .collect();
+                return Err(Error::FrameMismatch { keys });
+            }
+            first.ids().collect()
+        }
+    };
+    Ok(ids)
+}
+
+fn open_image(path: &Path) -> Result<DynamicImage, Error> {
+    let image = ImageReader::open(path)?.decode()?;
+    Ok(image)
+}
+
+/// Lists the names of sub-directories in sorted order.
+fn list_dir_names(dir: &Path) -> Result<Vec<String>, Error> {
+    let mut names: Vec<String> = dir
+        .read_dir()?
+        .map(|entry| -> Result<_, Error> {
+            let path = entry?.path();
+
+            if !path.is_dir() {
+                return Ok(None);
+            }
+
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string());
+            Ok(name)
+        })
+        .flatten_ok()
+        .try_collect()?;
+    names.sort();
+    Ok(names)
+}
+
 fn probe_max_frames(dir: &Path, width: usize, ext: Option<&str>) -> usize {
     let powers = || iter::successors(Some(1u64), |prev| Some(prev * 2));
     let path = |idx| match ext {
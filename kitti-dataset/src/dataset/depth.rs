@@ -0,0 +1,299 @@
+use crate::{
+    common::indexed_files,
+    depth::{DepthMap, Intrinsics},
+    Error,
+};
+use image::DynamicImage;
+use itertools::Itertools;
+use std::path::{Path, PathBuf};
+
+/// The sub-directory of semi-dense ground truth depth maps.
+const GROUNDTRUTH_DIR: &str = "groundtruth_depth";
+
+/// The sub-directory of sparse projected Velodyne depth maps.
+const VELODYNE_RAW_DIR: &str = "velodyne_raw";
+
+/// The sub-directory of RGB images.
+const IMAGE_DIR: &str = "image";
+
+/// The sub-directory of camera matrices.
+const INTRINSICS_DIR: &str = "intrinsics";
+
+/// The drive sub-directory of projected depth maps in the train and
+/// val sets.
+const PROJ_DEPTH_DIR: &str = "proj_depth";
+
+/// The `proj_depth` sub-directory of semi-dense ground truth depth
+/// maps.
+const PROJ_GROUNDTRUTH_DIR: &str = "groundtruth";
+
+/// The dataset of _Depth Completion and Prediction Evaluation_ in the
+/// `val_selection_cropped` and `test_*_anonymous` layouts, or in the
+/// `train` and `val` layout of projected depth maps.
+///
+/// ```ignore
+/// val_selection_cropped
+/// ├── groundtruth_depth
+/// ├── image
+/// ├── intrinsics
+/// └── velodyne_raw
+///
+/// train
+/// └── 2011_09_26_drive_0001_sync
+///     └── proj_depth
+///         ├── groundtruth
+///         │   ├── image_02
+///         │   └── image_03
+///         └── velodyne_raw
+///             ├── image_02
+///             └── image_03
+/// ```
+///
+/// Samples are paired as the devkit does. The files in the first
+/// present directory among `groundtruth_depth`, `velodyne_raw` and
+/// `image` are listed in sorted order, and the paired file names are
+/// derived by replacing the directory name in the file name, e.g.
+/// `..._sync_groundtruth_depth_0000000005_image_02.png` pairs with
+/// `..._sync_image_0000000005_image_02.png`. The intrinsics file is
+/// named after the image with the `.txt` extension. The test sets use
+/// the same file name in every directory.
+///
+/// In the `train` and `val` layout, the frames in `groundtruth`, or in
+/// `velodyne_raw` if ground truth is absent, are listed by drive,
+/// camera and frame number. The sparse depth map and the RGB image in
+/// the raw data, `<date>/<drive>/image_0x/data/<frame>.png`, are
+/// paired by the same drive, camera and frame number. The frames are
+/// named as in `val_selection_cropped`, and have no intrinsics.
+#[derive(Debug, Clone)]
+pub struct DepthDataset {
+    frames: Vec<FrameEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct FrameEntry {
+    name: String,
+    groundtruth: Option<PathBuf>,
+    velodyne_raw: Option<PathBuf>,
+    image: Option<PathBuf>,
+    intrinsics: Option<PathBuf>,
+}
+
+impl DepthDataset {
+    /// Opens the dataset directory. It fails with
+    /// [Error::MissingPairedFile] if a sub-directory lacks a file
+    /// paired with a listed sample. Frames in the `train` and `val`
+    /// layout have no images.
+    pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_impl(dir.as_ref(), None)
+    }
+
+    /// Opens a `train` or `val` directory of projected depth maps and
+    /// pairs the frames with the RGB images in the raw data directory,
+    /// which contains the recording dates. The raw data directory is
+    /// ignored in the `val_selection_cropped` and `test_*_anonymous`
+    /// layouts.
+    pub fn open_with_raw_dir<P, Q>(dir: P, raw_dir: Q) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+    {
+        Self::open_impl(dir.as_ref(), Some(raw_dir.as_ref()))
+    }
+
+    fn open_impl(dataset_dir: &Path, raw_dir: Option<&Path>) -> Result<Self, Error> {
+        let sub_dir = |name: &str| {
+            let path = dataset_dir.join(name);
+            path.is_dir().then_some(path)
+        };
+
+        let groundtruth_dir = sub_dir(GROUNDTRUTH_DIR);
+        let velodyne_raw_dir = sub_dir(VELODYNE_RAW_DIR);
+        let image_dir = sub_dir(IMAGE_DIR);
+        let intrinsics_dir = sub_dir(INTRINSICS_DIR);
+
+        let primary = [
+            (GROUNDTRUTH_DIR, &groundtruth_dir),
+            (VELODYNE_RAW_DIR, &velodyne_raw_dir),
+            (IMAGE_DIR, &image_dir),
+        ]
+        .into_iter()
+        .find_map(|(token, dir)| Some((token, dir.as_ref()?)));
+        let Some((primary_token, primary_dir)) = primary else {
+            return Self::open_proj_depth(dataset_dir, raw_dir);
+        };
+
+        let frames: Vec<FrameEntry> = list_png_names(primary_dir)?
+            .into_iter()
+            .map(|file_name| -> Result<_, Error> {
+                let paired = |dir: &Option<PathBuf>, token: &str, ext: &str| {
+                    let Some(dir) = dir else {
+                        return Ok(None);
+                    };
+                    let name = file_name.replacen(primary_token, token, 1);
+                    let name = match name.strip_suffix(".png") {
+                        Some(stem) => format!("{stem}.{ext}"),
+                        None => name,
+                    };
+                    let path = dir.join(name);
+                    if !path.is_file() {
+                        return Err(Error::MissingPairedFile(path));
+                    }
+                    Ok(Some(path))
+                };
+
+                Ok(FrameEntry {
+                    groundtruth: paired(&groundtruth_dir, GROUNDTRUTH_DIR, "png")?,
+                    velodyne_raw: paired(&velodyne_raw_dir, VELODYNE_RAW_DIR, "png")?,
+                    image: paired(&image_dir, IMAGE_DIR, "png")?,
+                    intrinsics: paired(&intrinsics_dir, IMAGE_DIR, "txt")?,
+                    name: file_name,
+                })
+            })
+            .try_collect()?;
+
+        Ok(Self { frames })
+    }
+
+    fn open_proj_depth(dataset_dir: &Path, raw_dir: Option<&Path>) -> Result<Self, Error> {
+        let mut frames = vec![];
+
+        for drive in super::list_dir_names(dataset_dir)? {
+            let proj_dir = dataset_dir.join(&drive).join(PROJ_DEPTH_DIR);
+            if !proj_dir.is_dir() {
+                continue;
+            }
+            let groundtruth_dir = proj_dir.join(PROJ_GROUNDTRUTH_DIR);
+            let velodyne_raw_dir = proj_dir.join(VELODYNE_RAW_DIR);
+            let (primary_token, primary_dir) = if groundtruth_dir.is_dir() {
+                (GROUNDTRUTH_DIR, &groundtruth_dir)
+            } else {
+                (VELODYNE_RAW_DIR, &velodyne_raw_dir)
+            };
+            if !primary_dir.is_dir() {
+                continue;
+            }
+            let date = drive.split("_drive_").next().unwrap_or(&drive);
+
+            for camera in super::list_dir_names(primary_dir)? {
+                if !camera.starts_with("image_") {
+                    continue;
+                }
+
+                for frame_id in
+                    indexed_files::list_indexed_files(&primary_dir.join(&camera), "png")?
+                        .into_keys()
+                {
+                    let file_name = format!("{frame_id:010}.png");
+                    let paired = |path: PathBuf| {
+                        if !path.is_file() {
+                            return Err(Error::MissingPairedFile(path));
+                        }
+                        Ok(path)
+                    };
+                    let proj_path = |dir: &Path| dir.join(&camera).join(&file_name);
+
+                    let groundtruth = groundtruth_dir
+                        .is_dir()
+                        .then(|| paired(proj_path(&groundtruth_dir)))
+                        .transpose()?;
+                    let velodyne_raw = velodyne_raw_dir
+                        .is_dir()
+                        .then(|| paired(proj_path(&velodyne_raw_dir)))
+                        .transpose()?;
+                    let image = raw_dir
+                        .map(|raw_dir| {
+                            let drive_dir = raw_dir.join(date).join(&drive);
+                            paired(drive_dir.join(&camera).join("data").join(&file_name))
+                        })
+                        .transpose()?;
+
+                    frames.push(FrameEntry {
+                        name: format!("{drive}_{primary_token}_{frame_id:010}_{camera}.png"),
+                        groundtruth,
+                        velodyne_raw,
+                        image,
+                        intrinsics: None,
+                    });
+                }
+            }
+        }
+
+        Ok(Self { frames })
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'_>> {
+        self.frames.iter().map(|entry| Frame { entry })
+    }
+
+    pub fn frame(&self, frame_idx: usize) -> Option<Frame<'_>> {
+        let entry = self.frames.get(frame_idx)?;
+        Some(Frame { entry })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame<'a> {
+    entry: &'a FrameEntry,
+}
+
+impl<'a> Frame<'a> {
+    /// Gets the file name of the listed sample, which is the ground
+    /// truth if available.
+    pub fn name(&self) -> &'a str {
+        &self.entry.name
+    }
+
+    pub fn groundtruth_path(&self) -> Option<&'a Path> {
+        self.entry.groundtruth.as_deref()
+    }
+
+    pub fn velodyne_raw_path(&self) -> Option<&'a Path> {
+        self.entry.velodyne_raw.as_deref()
+    }
+
+    pub fn image_path(&self) -> Option<&'a Path> {
+        self.entry.image.as_deref()
+    }
+
+    pub fn intrinsics_path(&self) -> Option<&'a Path> {
+        self.entry.intrinsics.as_deref()
+    }
+
+    /// Loads the semi-dense ground truth depth map.
+    pub fn groundtruth(&self) -> Result<Option<DepthMap>, Error> {
+        self.groundtruth_path().map(DepthMap::from_path).transpose()
+    }
+
+    /// Loads the sparse projected Velodyne depth map.
+    pub fn velodyne_raw(&self) -> Result<Option<DepthMap>, Error> {
+        self.velodyne_raw_path()
+            .map(DepthMap::from_path)
+            .transpose()
+    }
+
+    pub fn image(&self) -> Result<Option<DynamicImage>, Error> {
+        self.image_path().map(super::open_image).transpose()
+    }
+
+    pub fn intrinsics(&self) -> Result<Option<Intrinsics>, Error> {
+        self.intrinsics_path()
+            .map(Intrinsics::from_path)
+            .transpose()
+    }
+}
+
+/// Lists the `.png` file names in a directory in sorted order.
+fn list_png_names(dir: &Path) -> Result<Vec<String>, Error> {
+    let names = indexed_files::list_files_by_stem(dir, "png")?
+        .into_keys()
+        .map(|stem| format!("{stem}.png"))
+        .collect();
+    Ok(names)
+}
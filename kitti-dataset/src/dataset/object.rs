@@ -1,30 +1,57 @@
 use crate::{
-    common::PointCloud,
-    object::{Calibration, Label},
+    common::{PointCloud, PointCloudView},
+    dataset::{DirIndex, MismatchPolicy, OpenOptions},
+    object::{Calibration, Label, Split},
     Error,
 };
 use image::DynamicImage;
 use itertools::Itertools;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     path::{Path, PathBuf},
 };
 
 #[derive(Debug, Clone)]
 pub struct ObjectDataset {
     dataset_dir: PathBuf,
-    num_frames: usize,
-    sub_dirs: HashMap<String, DataKind>,
+    frame_ids: Vec<usize>,
+    sub_dirs: BTreeMap<String, SubDir>,
+}
+
+#[derive(Debug, Clone)]
+struct SubDir {
+    kind: DataKind,
+    index: DirIndex,
 }
 
 impl ObjectDataset {
+    /// Opens the dataset directory with default [OpenOptions]. The
+    /// frames are those available in all sub-directories.
     pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::default())
+    }
+
+    /// Opens the dataset directory with a policy for sub-directories
+    /// containing different frames.
+    pub fn open_with<P>(dir: P, policy: MismatchPolicy) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::new().with_mismatch_policy(policy))
+    }
+
+    /// Opens the dataset directory with options to index
+    /// sub-directories and to resolve mismatched frames.
+    pub fn open_with_options<P>(dir: P, options: OpenOptions) -> Result<Self, Error>
     where
         P: AsRef<Path>,
     {
         let dataset_dir = dir.as_ref();
 
-        let sub_dirs: HashMap<String, DataKind> = dataset_dir
+        let sub_dirs: BTreeMap<String, SubDir> = dataset_dir
             .read_dir()?
             .map(|entry| -> Result<_, Error> {
                 macro_rules! skip {
@@ -59,86 +86,149 @@ impl ObjectDataset {
                     skip!();
                 };
 
-                Ok(Some((file_name.to_string(), kind)))
+                let index = DirIndex::open(&path, 6, Some(kind.file_ext()), options.index_mode())?;
+                Ok(Some((file_name.to_string(), SubDir { kind, index })))
             })
             .flatten_ok()
             .try_collect()?;
 
-        let num_frames = match sub_dirs.iter().next() {
-            Some((key, kind)) => {
-                super::probe_max_frames(&dataset_dir.join(key), 6, Some(kind.file_ext()))
-            }
-            None => 0,
-        };
+        let frame_ids = super::resolve_frame_ids(
+            sub_dirs
+                .iter()
+                .map(|(key, sub_dir)| (key.as_str(), &sub_dir.index)),
+            options.mismatch_policy(),
+        )?;
 
         Ok(Self {
-            num_frames,
+            frame_ids,
             sub_dirs,
             dataset_dir: dataset_dir.to_owned(),
         })
     }
 
     pub fn num_frames(&self) -> usize {
-        self.num_frames
+        self.frame_ids.len()
+    }
+
+    /// Gets the KITTI frame IDs in ascending order.
+    pub fn frame_ids(&self) -> &[usize] {
+        &self.frame_ids
     }
 
     pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'_>> {
-        (0..self.num_frames).map(|frame_idx| Frame {
+        self.frame_ids.iter().map(|&frame_id| Frame {
             dataset: self,
-            frame_idx,
+            frame_id,
         })
     }
 
+    /// Gets the frame at the position in [frame_ids()](Self::frame_ids).
+    /// The position is the frame ID if IDs are contiguous.
     pub fn frame(&self, frame_idx: usize) -> Option<Frame<'_>> {
-        if frame_idx >= self.num_frames {
-            return None;
-        }
+        let frame_id = *self.frame_ids.get(frame_idx)?;
+        Some(Frame {
+            dataset: self,
+            frame_id,
+        })
+    }
 
+    /// Gets the frame by its KITTI frame ID, which is the number in
+    /// the file names.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Frame<'_>> {
+        self.frame_ids.binary_search(&frame_id).ok()?;
         Some(Frame {
             dataset: self,
-            frame_idx,
+            frame_id,
         })
     }
 
+    /// Creates a view on the frames in the split, such as the IDs in
+    /// `ImageSets/train.txt`. IDs not found in the dataset are
+    /// skipped.
+    ///
+    /// ```ignore
+    /// let split = Split::from_path("object/ImageSets/train.txt")?;
+    /// let train = dataset.subset(&split);
+    /// ```
+    pub fn subset(&self, split: &Split) -> Self {
+        let frame_ids = self
+            .frame_ids
+            .iter()
+            .copied()
+            .filter(|&id| split.contains(id))
+            .collect();
+
+        Self {
+            dataset_dir: self.dataset_dir.clone(),
+            frame_ids,
+            sub_dirs: self.sub_dirs.clone(),
+        }
+    }
+
     pub fn key(&self, key: &str) -> Option<KeyEntry<'_>> {
-        let kind = *self.sub_dirs.get(key)?;
+        let (key, sub_dir) = self.sub_dirs.get_key_value(key)?;
         Some(KeyEntry {
             dataset: self,
-            key: key.to_string(),
-            kind,
+            key,
+            sub_dir,
         })
     }
 
+    /// Iterates over sub-directory keys in sorted order.
     pub fn keys(&self) -> impl Iterator<Item = (&str, DataKind)> {
-        self.sub_dirs.iter().map(|(s, &k)| (s.as_str(), k))
+        self.sub_dirs
+            .iter()
+            .map(|(s, sub_dir)| (s.as_str(), sub_dir.kind))
+    }
+
+    /// Gets the number of frames found in each sub-directory in sorted
+    /// order of keys.
+    pub fn key_num_frames(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.sub_dirs
+            .iter()
+            .map(|(s, sub_dir)| (s.as_str(), sub_dir.index.len()))
+    }
+
+    /// Lists the sub-directories having the frame.
+    fn sub_dirs_at(&self, frame_id: usize) -> impl Iterator<Item = (&String, DataKind)> {
+        self.sub_dirs
+            .iter()
+            .filter(move |(_, sub_dir)| sub_dir.index.contains(frame_id))
+            .map(|(key, sub_dir)| (key, sub_dir.kind))
+    }
+
+    fn sample_path(&self, key: &str, kind: DataKind, frame_id: usize) -> PathBuf {
+        self.dataset_dir
+            .join(key)
+            .join(format!("{:06}.{}", frame_id, kind.file_ext()))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Frame<'a> {
     dataset: &'a ObjectDataset,
-    frame_idx: usize,
+    frame_id: usize,
 }
 
 impl<'a> Frame<'a> {
+    /// Gets the KITTI frame ID.
+    pub fn id(&self) -> usize {
+        self.frame_id
+    }
+
     pub fn key(&self, key: &str) -> Option<Sample> {
-        let kind = *self.dataset.sub_dirs.get(key)?;
-        let path = self.dataset.dataset_dir.join(key).join(format!(
-            "{:06}.{}",
-            self.frame_idx,
-            kind.file_ext()
-        ));
-        let sample = Sample { kind, path };
+        let SubDir { kind, index } = self.dataset.sub_dirs.get(key)?;
+        if !index.contains(self.frame_id) {
+            return None;
+        }
+        let path = self.dataset.sample_path(key, *kind, self.frame_id);
+        let sample = Sample { kind: *kind, path };
         Some(sample)
     }
 
     pub fn sample_iter(&self) -> impl Iterator<Item = Sample> + '_ {
-        self.dataset.sub_dirs.iter().map(|(key, &kind)| {
-            let path = self.dataset.dataset_dir.join(key).join(format!(
-                "{:06}.{}",
-                self.frame_idx,
-                kind.file_ext()
-            ));
+        self.dataset.sub_dirs_at(self.frame_id).map(|(key, kind)| {
+            let path = self.dataset.sample_path(key, kind, self.frame_id);
             Sample { kind, path }
         })
     }
@@ -147,27 +237,48 @@ impl<'a> Frame<'a> {
 #[derive(Debug, Clone)]
 pub struct KeyEntry<'a> {
     dataset: &'a ObjectDataset,
-    key: String,
-    kind: DataKind,
+    key: &'a str,
+    sub_dir: &'a SubDir,
 }
 
 impl<'a> KeyEntry<'a> {
+    pub fn kind(&self) -> DataKind {
+        self.sub_dir.kind
+    }
+
+    /// Gets the number of frames found in the sub-directory. It can
+    /// differ from the dataset frame count if sub-directories are
+    /// mismatched.
+    pub fn num_frames(&self) -> usize {
+        self.sub_dir.index.len()
+    }
+
+    /// Gets the frame IDs, gaps and stray files in the sub-directory.
+    pub fn index(&self) -> &'a DirIndex {
+        &self.sub_dir.index
+    }
+
+    /// Gets the sample of the frame at the position in
+    /// [ObjectDataset::frame_ids()].
     pub fn frame(&self, frame_idx: usize) -> Option<Sample> {
-        if frame_idx >= self.dataset.num_frames {
+        let frame_id = *self.dataset.frame_ids.get(frame_idx)?;
+        self.sample(frame_id)
+    }
+
+    /// Gets the sample by the KITTI frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Sample> {
+        self.dataset.frame_ids.binary_search(&frame_id).ok()?;
+        self.sample(frame_id)
+    }
+
+    fn sample(&self, frame_id: usize) -> Option<Sample> {
+        if !self.sub_dir.index.contains(frame_id) {
             return None;
         }
 
-        let path = self.dataset.dataset_dir.join(&self.key).join(format!(
-            "{:06}.{}",
-            frame_idx,
-            self.kind.file_ext()
-        ));
-
-        let sample = Sample {
-            kind: self.kind,
-            path,
-        };
-        Some(sample)
+        let kind = self.sub_dir.kind;
+        let path = self.dataset.sample_path(self.key, kind, frame_id);
+        Some(Sample { kind, path })
     }
 }
 
@@ -208,6 +319,16 @@ impl Sample {
     pub fn data(&self) -> Result<SampleData, Error> {
         SampleData::open(self.kind, &self.path)
     }
+
+    /// Memory-maps the point cloud file. It returns `None` if the
+    /// sample is not a Velodyne sample.
+    pub fn point_cloud_view(&self) -> Result<Option<PointCloudView>, Error> {
+        if self.kind != DataKind::Velodyne {
+            return Ok(None);
+        }
+        let view = PointCloudView::open(&self.path)?;
+        Ok(Some(view))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -225,7 +346,7 @@ impl SampleData {
     {
         let sample = match kind {
             DataKind::Image => {
-                let image = image::io::Reader::open(path)?.decode()?;
+                let image = super::open_image(path.as_ref())?;
                 SampleData::Image(Box::new(image))
             }
             DataKind::Velodyne => {
@@ -0,0 +1,403 @@
+use crate::{
+    common::{PointCloud, PointCloudView, TimeOffset},
+    dataset::{DirIndex, MismatchPolicy, OpenOptions},
+    odometry::{Calibration, Pose},
+    Error,
+};
+use image::DynamicImage;
+use itertools::Itertools;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct OdometryDataset {
+    dataset_dir: PathBuf,
+    sequences: Vec<SequenceEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct SequenceEntry {
+    name: String,
+    frame_ids: Vec<usize>,
+    sub_dirs: Vec<SubDir>,
+    times: Vec<TimeOffset>,
+    poses: Option<Vec<Pose>>,
+}
+
+#[derive(Debug, Clone)]
+struct SubDir {
+    key: String,
+    kind: DataKind,
+    index: DirIndex,
+}
+
+impl OdometryDataset {
+    /// Opens the dataset directory containing `sequences` and
+    /// optionally `poses` sub-directories with default
+    /// [OpenOptions]. The frames of a sequence are those available in
+    /// all sensor directories.
+    pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::default())
+    }
+
+    /// Opens the dataset directory with a policy for sensor
+    /// directories containing different frames.
+    pub fn open_with<P>(dir: P, policy: MismatchPolicy) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::new().with_mismatch_policy(policy))
+    }
+
+    /// Opens the dataset directory with options to index sensor
+    /// directories and to resolve mismatched frames.
+    pub fn open_with_options<P>(dir: P, options: OpenOptions) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dataset_dir = dir.as_ref();
+        let sequences_dir = dataset_dir.join("sequences");
+        let poses_dir = dataset_dir.join("poses");
+
+        let sequences: Vec<SequenceEntry> = super::list_dir_names(&sequences_dir)?
+            .into_iter()
+            .filter(|name| name.bytes().all(|b| b.is_ascii_digit()))
+            .map(|name| SequenceEntry::open(&sequences_dir, &poses_dir, name, options))
+            .try_collect()?;
+
+        Ok(Self {
+            dataset_dir: dataset_dir.to_owned(),
+            sequences,
+        })
+    }
+
+    pub fn num_sequences(&self) -> usize {
+        self.sequences.len()
+    }
+
+    pub fn sequence_iter(&self) -> impl Iterator<Item = Sequence<'_>> {
+        self.sequences.iter().map(|entry| Sequence {
+            dataset: self,
+            entry,
+        })
+    }
+
+    /// Gets the sequence by its directory name, e.g. `00`.
+    pub fn sequence(&self, name: &str) -> Option<Sequence<'_>> {
+        let entry = self.sequences.iter().find(|entry| entry.name == name)?;
+        Some(Sequence {
+            dataset: self,
+            entry,
+        })
+    }
+}
+
+impl SequenceEntry {
+    fn open(
+        sequences_dir: &Path,
+        poses_dir: &Path,
+        name: String,
+        options: OpenOptions,
+    ) -> Result<Self, Error> {
+        let seq_dir = sequences_dir.join(&name);
+
+        let sub_dirs: Vec<SubDir> = super::list_dir_names(&seq_dir)?
+            .into_iter()
+            .filter_map(|file_name| {
+                let kind = if file_name.starts_with("image") {
+                    DataKind::Image
+                } else if file_name.starts_with("velodyne") {
+                    DataKind::Velodyne
+                } else {
+                    return None;
+                };
+                Some((file_name, kind))
+            })
+            .map(|(key, kind)| -> Result<_, Error> {
+                let index = DirIndex::open(
+                    &seq_dir.join(&key),
+                    6,
+                    Some(kind.file_ext()),
+                    options.index_mode(),
+                )?;
+                Ok(SubDir { key, kind, index })
+            })
+            .try_collect()?;
+
+        let frame_ids = super::resolve_frame_ids(
+            sub_dirs
+                .iter()
+                .map(|sub_dir| (sub_dir.key.as_str(), &sub_dir.index)),
+            options.mismatch_policy(),
+        )?;
+
+        let times = TimeOffset::vec_from_path(seq_dir.join("times.txt"))?;
+
+        let pose_path = poses_dir.join(format!("{name}.txt"));
+        let poses = if pose_path.is_file() {
+            Some(Pose::vec_from_path(pose_path)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            name,
+            frame_ids,
+            sub_dirs,
+            times,
+            poses,
+        })
+    }
+
+    fn sub_dir(&self, key: &str) -> Option<&SubDir> {
+        self.sub_dirs.iter().find(|sub_dir| sub_dir.key == key)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sequence<'a> {
+    dataset: &'a OdometryDataset,
+    entry: &'a SequenceEntry,
+}
+
+impl<'a> Sequence<'a> {
+    pub fn name(&self) -> &str {
+        &self.entry.name
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.dataset
+            .dataset_dir
+            .join("sequences")
+            .join(&self.entry.name)
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.entry.frame_ids.len()
+    }
+
+    /// Gets the frame IDs in ascending order, which are the numbers in
+    /// the file names.
+    pub fn frame_ids(&self) -> &'a [usize] {
+        &self.entry.frame_ids
+    }
+
+    pub fn calibration(&self) -> Result<Calibration, Error> {
+        Calibration::from_path(self.dir().join("calib.txt"))
+    }
+
+    /// Gets the frame times in `times.txt`.
+    pub fn times(&self) -> &'a [TimeOffset] {
+        &self.entry.times
+    }
+
+    /// Gets the ground truth poses. It returns `None` if the
+    /// sequence has no pose file.
+    pub fn poses(&self) -> Option<&'a [Pose]> {
+        self.entry.poses.as_deref()
+    }
+
+    pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'a>> + '_ {
+        self.entry.frame_ids.iter().map(|&frame_id| Frame {
+            sequence: self.clone(),
+            frame_id,
+        })
+    }
+
+    /// Gets the frame at the position in [frame_ids()](Self::frame_ids).
+    /// The position is the frame ID if IDs are contiguous.
+    pub fn frame(&self, frame_idx: usize) -> Option<Frame<'a>> {
+        let frame_id = *self.entry.frame_ids.get(frame_idx)?;
+        Some(Frame {
+            sequence: self.clone(),
+            frame_id,
+        })
+    }
+
+    /// Gets the frame by its frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Frame<'a>> {
+        self.entry.frame_ids.binary_search(&frame_id).ok()?;
+        Some(Frame {
+            sequence: self.clone(),
+            frame_id,
+        })
+    }
+
+    pub fn key(&self, key: &str) -> Option<KeyEntry<'a>> {
+        let sub_dir = self.entry.sub_dir(key)?;
+        Some(KeyEntry {
+            sequence: self.clone(),
+            sub_dir,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = (&str, DataKind)> {
+        self.entry
+            .sub_dirs
+            .iter()
+            .map(|sub_dir| (sub_dir.key.as_str(), sub_dir.kind))
+    }
+
+    fn sample(&self, sub_dir: &SubDir, frame_id: usize) -> Option<Sample> {
+        if !sub_dir.index.contains(frame_id) {
+            return None;
+        }
+
+        let SubDir { key, kind, .. } = sub_dir;
+        let path = self
+            .dir()
+            .join(key)
+            .join(format!("{frame_id:06}.{}", kind.file_ext()));
+        Some(Sample { kind: *kind, path })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame<'a> {
+    sequence: Sequence<'a>,
+    frame_id: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Gets the frame ID.
+    pub fn id(&self) -> usize {
+        self.frame_id
+    }
+
+    pub fn key(&self, key: &str) -> Option<Sample> {
+        let sub_dir = self.sequence.entry.sub_dir(key)?;
+        self.sequence.sample(sub_dir, self.frame_id)
+    }
+
+    pub fn sample_iter(&self) -> impl Iterator<Item = Sample> + '_ {
+        self.sequence
+            .entry
+            .sub_dirs
+            .iter()
+            .filter_map(|sub_dir| self.sequence.sample(sub_dir, self.frame_id))
+    }
+
+    /// Gets the frame time since the start of the sequence.
+    pub fn timestamp(&self) -> Option<&'a TimeOffset> {
+        self.sequence.times().get(self.frame_id)
+    }
+
+    /// Gets the ground truth pose if available.
+    pub fn pose(&self) -> Option<&'a Pose> {
+        self.sequence.poses()?.get(self.frame_id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyEntry<'a> {
+    sequence: Sequence<'a>,
+    sub_dir: &'a SubDir,
+}
+
+impl<'a> KeyEntry<'a> {
+    pub fn kind(&self) -> DataKind {
+        self.sub_dir.kind
+    }
+
+    /// Gets the number of frames found in the sensor directory. It can
+    /// differ from the sequence frame count if sensor directories are
+    /// mismatched.
+    pub fn num_frames(&self) -> usize {
+        self.sub_dir.index.len()
+    }
+
+    /// Gets the frame IDs, gaps and stray files in the sensor
+    /// directory.
+    pub fn index(&self) -> &'a DirIndex {
+        &self.sub_dir.index
+    }
+
+    /// Gets the sample of the frame at the position in
+    /// [Sequence::frame_ids()].
+    pub fn frame(&self, frame_idx: usize) -> Option<Sample> {
+        let frame_id = *self.sequence.entry.frame_ids.get(frame_idx)?;
+        self.sequence.sample(self.sub_dir, frame_id)
+    }
+
+    /// Gets the sample by the frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Sample> {
+        self.sequence
+            .entry
+            .frame_ids
+            .binary_search(&frame_id)
+            .ok()?;
+        self.sequence.sample(self.sub_dir, frame_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    Image,
+    Velodyne,
+}
+
+impl DataKind {
+    fn file_ext(&self) -> &str {
+        match self {
+            DataKind::Image => "png",
+            DataKind::Velodyne => "bin",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    kind: DataKind,
+    path: PathBuf,
+}
+
+impl Sample {
+    pub fn kind(&self) -> DataKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn data(&self) -> Result<SampleData, Error> {
+        SampleData::open(self.kind, &self.path)
+    }
+
+    /// Memory-maps the point cloud file. It returns `None` if the
+    /// sample is not a Velodyne sample.
+    pub fn point_cloud_view(&self) -> Result<Option<PointCloudView>, Error> {
+        if self.kind != DataKind::Velodyne {
+            return Ok(None);
+        }
+        let view = PointCloudView::open(&self.path)?;
+        Ok(Some(view))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SampleData {
+    Image(Box<DynamicImage>),
+    Velodyne(Box<PointCloud>),
+}
+
+impl SampleData {
+    pub fn open<P>(kind: DataKind, path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let sample = match kind {
+            DataKind::Image => {
+                let image = super::open_image(path.as_ref())?;
+                SampleData::Image(Box::new(image))
+            }
+            DataKind::Velodyne => {
+                let pcd = PointCloud::from_path(path)?;
+                SampleData::Velodyne(Box::new(pcd))
+            }
+        };
+        Ok(sample)
+    }
+}
@@ -0,0 +1,446 @@
+use crate::{
+    common::{PointCloud, PointCloudView, Timestamp},
+    dataset::{DirIndex, MismatchPolicy, OpenOptions},
+    raw::Calibration,
+    tracking::Oxts,
+    Error,
+};
+use image::DynamicImage;
+use itertools::Itertools;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone)]
+pub struct RawDataset {
+    dataset_dir: PathBuf,
+    drives: Vec<DriveEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct DriveEntry {
+    date: String,
+    name: String,
+    frame_ids: Vec<usize>,
+    sub_dirs: Vec<SubDir>,
+}
+
+#[derive(Debug, Clone)]
+struct SubDir {
+    key: String,
+    kind: DataKind,
+    index: DirIndex,
+    timestamps: Option<Vec<Timestamp>>,
+}
+
+impl RawDataset {
+    /// Opens the dataset directory with default [OpenOptions]. The
+    /// frames of a drive are those available in all sensor
+    /// directories.
+    pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::default())
+    }
+
+    /// Opens the dataset directory with a policy for sensor
+    /// directories containing different frames.
+    pub fn open_with<P>(dir: P, policy: MismatchPolicy) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::new().with_mismatch_policy(policy))
+    }
+
+    /// Opens the dataset directory with options to index sensor
+    /// directories and to resolve mismatched frames.
+    pub fn open_with_options<P>(dir: P, options: OpenOptions) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dataset_dir = dir.as_ref();
+
+        let dates: Vec<String> = super::list_dir_names(dataset_dir)?
+            .into_iter()
+            .filter(|name| is_date_name(name))
+            .collect();
+
+        let drives: Vec<DriveEntry> = dates
+            .into_iter()
+            .map(|date| -> Result<_, Error> {
+                let date_dir = dataset_dir.join(&date);
+                let prefix = format!("{date}_drive_");
+
+                let drives: Vec<_> = super::list_dir_names(&date_dir)?
+                    .into_iter()
+                    .filter(|name| name.starts_with(&prefix))
+                    .map(|name| DriveEntry::open(&date_dir, &date, name, options))
+                    .try_collect()?;
+                Ok(drives)
+            })
+            .flatten_ok()
+            .try_collect()?;
+
+        Ok(Self {
+            dataset_dir: dataset_dir.to_owned(),
+            drives,
+        })
+    }
+
+    pub fn num_drives(&self) -> usize {
+        self.drives.len()
+    }
+
+    pub fn dates(&self) -> impl Iterator<Item = &str> {
+        self.drives.iter().map(|entry| entry.date.as_str()).dedup()
+    }
+
+    pub fn drive_iter(&self) -> impl Iterator<Item = Drive<'_>> {
+        self.drives.iter().map(|entry| Drive {
+            dataset: self,
+            entry,
+        })
+    }
+
+    /// Gets the drive by its directory name, e.g.
+    /// `2011_09_26_drive_0001_sync`.
+    pub fn drive(&self, name: &str) -> Option<Drive<'_>> {
+        let entry = self.drives.iter().find(|entry| entry.name == name)?;
+        Some(Drive {
+            dataset: self,
+            entry,
+        })
+    }
+}
+
+impl DriveEntry {
+    fn open(
+        date_dir: &Path,
+        date: &str,
+        name: String,
+        options: OpenOptions,
+    ) -> Result<Self, Error> {
+        let drive_dir = date_dir.join(&name);
+
+        let sub_dirs: Vec<SubDir> = super::list_dir_names(&drive_dir)?
+            .into_iter()
+            .filter_map(|file_name| {
+                let kind = if file_name.starts_with("image") {
+                    DataKind::Image
+                } else if file_name.starts_with("velodyne") {
+                    DataKind::Velodyne
+                } else if file_name.starts_with("oxts") {
+                    DataKind::Oxts
+                } else {
+                    return None;
+                };
+                Some((file_name, kind))
+            })
+            .map(|(key, kind)| -> Result<_, Error> {
+                let key_dir = drive_dir.join(&key);
+                let index = DirIndex::open(
+                    &key_dir.join("data"),
+                    10,
+                    Some(kind.file_ext()),
+                    options.index_mode(),
+                )?;
+
+                let timestamps_path = key_dir.join("timestamps.txt");
+                let timestamps = if timestamps_path.is_file() {
+                    Some(Timestamp::vec_from_path(timestamps_path)?)
+                } else {
+                    None
+                };
+
+                Ok(SubDir {
+                    key,
+                    kind,
+                    index,
+                    timestamps,
+                })
+            })
+            .try_collect()?;
+
+        let frame_ids = super::resolve_frame_ids(
+            sub_dirs
+                .iter()
+                .map(|sub_dir| (sub_dir.key.as_str(), &sub_dir.index)),
+            options.mismatch_policy(),
+        )?;
+
+        Ok(Self {
+            date: date.to_string(),
+            name,
+            frame_ids,
+            sub_dirs,
+        })
+    }
+
+    fn sub_dir(&self, key: &str) -> Option<&SubDir> {
+        self.sub_dirs.iter().find(|sub_dir| sub_dir.key == key)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Drive<'a> {
+    dataset: &'a RawDataset,
+    entry: &'a DriveEntry,
+}
+
+impl<'a> Drive<'a> {
+    pub fn date(&self) -> &str {
+        &self.entry.date
+    }
+
+    pub fn name(&self) -> &str {
+        &self.entry.name
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.dataset
+            .dataset_dir
+            .join(&self.entry.date)
+            .join(&self.entry.name)
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.entry.frame_ids.len()
+    }
+
+    /// Gets the frame IDs in ascending order, which are the numbers in
+    /// the file names.
+    pub fn frame_ids(&self) -> &'a [usize] {
+        &self.entry.frame_ids
+    }
+
+    /// Loads the calibration files of the recording date.
+    pub fn calibration(&self) -> Result<Calibration, Error> {
+        Calibration::from_dir(self.dataset.dataset_dir.join(&self.entry.date))
+    }
+
+    pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'a>> + '_ {
+        self.entry.frame_ids.iter().map(|&frame_id| Frame {
+            drive: self.clone(),
+            frame_id,
+        })
+    }
+
+    /// Gets the frame at the position in [frame_ids()](Self::frame_ids).
+    /// The position is the frame ID if IDs are contiguous.
+    pub fn frame(&self, frame_idx: usize) -> Option<Frame<'a>> {
+        let frame_id = *self.entry.frame_ids.get(frame_idx)?;
+        Some(Frame {
+            drive: self.clone(),
+            frame_id,
+        })
+    }
+
+    /// Gets the frame by its frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Frame<'a>> {
+        self.entry.frame_ids.binary_search(&frame_id).ok()?;
+        Some(Frame {
+            drive: self.clone(),
+            frame_id,
+        })
+    }
+
+    pub fn key(&self, key: &str) -> Option<KeyEntry<'a>> {
+        let sub_dir = self.entry.sub_dir(key)?;
+        Some(KeyEntry {
+            drive: self.clone(),
+            sub_dir,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = (&str, DataKind)> {
+        self.entry
+            .sub_dirs
+            .iter()
+            .map(|sub_dir| (sub_dir.key.as_str(), sub_dir.kind))
+    }
+
+    /// Gets the timestamps in `timestamps.txt` of a sensor
+    /// directory. It returns `None` if the key does not exist or the
+    /// directory has no timestamp file.
+    pub fn timestamps(&self, key: &str) -> Option<&'a [Timestamp]> {
+        self.entry.sub_dir(key)?.timestamps.as_deref()
+    }
+
+    fn sample(&self, sub_dir: &SubDir, frame_id: usize) -> Option<Sample> {
+        if !sub_dir.index.contains(frame_id) {
+            return None;
+        }
+
+        let SubDir { key, kind, .. } = sub_dir;
+        let path = self
+            .dir()
+            .join(key)
+            .join("data")
+            .join(format!("{frame_id:010}.{}", kind.file_ext()));
+        Some(Sample { kind: *kind, path })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame<'a> {
+    drive: Drive<'a>,
+    frame_id: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Gets the frame ID.
+    pub fn id(&self) -> usize {
+        self.frame_id
+    }
+
+    pub fn key(&self, key: &str) -> Option<Sample> {
+        let sub_dir = self.drive.entry.sub_dir(key)?;
+        self.drive.sample(sub_dir, self.frame_id)
+    }
+
+    pub fn sample_iter(&self) -> impl Iterator<Item = Sample> + '_ {
+        self.drive
+            .entry
+            .sub_dirs
+            .iter()
+            .filter_map(|sub_dir| self.drive.sample(sub_dir, self.frame_id))
+    }
+
+    /// Gets the capture time of the sample from a sensor directory.
+    pub fn timestamp(&self, key: &str) -> Option<&'a Timestamp> {
+        self.drive.timestamps(key)?.get(self.frame_id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyEntry<'a> {
+    drive: Drive<'a>,
+    sub_dir: &'a SubDir,
+}
+
+impl<'a> KeyEntry<'a> {
+    pub fn kind(&self) -> DataKind {
+        self.sub_dir.kind
+    }
+
+    /// Gets the number of frames found in the sensor directory. It can
+    /// differ from the drive frame count if sensor directories are
+    /// mismatched.
+    pub fn num_frames(&self) -> usize {
+        self.sub_dir.index.len()
+    }
+
+    /// Gets the frame IDs, gaps and stray files in the `data`
+    /// directory.
+    pub fn index(&self) -> &'a DirIndex {
+        &self.sub_dir.index
+    }
+
+    /// Gets the sample of the frame at the position in
+    /// [Drive::frame_ids()].
+    pub fn frame(&self, frame_idx: usize) -> Option<Sample> {
+        let frame_id = *self.drive.entry.frame_ids.get(frame_idx)?;
+        self.drive.sample(self.sub_dir, frame_id)
+    }
+
+    /// Gets the sample by the frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Sample> {
+        self.drive.entry.frame_ids.binary_search(&frame_id).ok()?;
+        self.drive.sample(self.sub_dir, frame_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    Image,
+    Velodyne,
+    Oxts,
+}
+
+impl DataKind {
+    fn file_ext(&self) -> &str {
+        match self {
+            DataKind::Image => "png",
+            DataKind::Velodyne => "bin",
+            DataKind::Oxts => "txt",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    kind: DataKind,
+    path: PathBuf,
+}
+
+impl Sample {
+    pub fn kind(&self) -> DataKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn data(&self) -> Result<SampleData, Error> {
+        SampleData::open(self.kind, &self.path)
+    }
+
+    /// Memory-maps the point cloud file. It returns `None` if the
+    /// sample is not a Velodyne sample.
+    pub fn point_cloud_view(&self) -> Result<Option<PointCloudView>, Error> {
+        if self.kind != DataKind::Velodyne {
+            return Ok(None);
+        }
+        let view = PointCloudView::open(&self.path)?;
+        Ok(Some(view))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SampleData {
+    Image(Box<DynamicImage>),
+    Velodyne(Box<PointCloud>),
+    Oxts(Box<Oxts>),
+}
+
+impl SampleData {
+    pub fn open<P>(kind: DataKind, path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let sample = match kind {
+            DataKind::Image => {
+                let image = super::open_image(path.as_ref())?;
+                SampleData::Image(Box::new(image))
+            }
+            DataKind::Velodyne => {
+                let pcd = PointCloud::from_path(path)?;
+                SampleData::Velodyne(Box::new(pcd))
+            }
+            DataKind::Oxts => {
+                let Some(oxts) = Oxts::vec_from_path(path)?.into_iter().next() else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "unexpected empty oxts file",
+                    )
+                    .into());
+                };
+                SampleData::Oxts(Box::new(oxts))
+            }
+        };
+        Ok(sample)
+    }
+}
+
+/// Checks if the name is in `YYYY_MM_DD` form.
+fn is_date_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(idx, &b)| match idx {
+            4 | 7 => b == b'_',
+            _ => b.is_ascii_digit(),
+        })
+}
@@ -0,0 +1,317 @@
+use crate::{
+    dataset::{DirIndex, MismatchPolicy, OpenOptions},
+    stereo::{DisparityMap, FlowField},
+    Error,
+};
+use image::{DynamicImage, GrayImage};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// The dataset of _Stereo Evaluation 2012/2015_, _Optical Flow
+/// Evaluation 2012/2015_ and _Scene Flow Evaluation 2015_.
+///
+/// A frame is a scene identified by its 6-digit ID. Each scene has a
+/// reference sample named `NNNNNN_10.png` and, for images, the next
+/// sample named `NNNNNN_11.png`.
+///
+/// ```ignore
+/// training
+/// ├── disp_noc_0
+/// ├── disp_noc_1
+/// ├── disp_occ_0
+/// ├── disp_occ_1
+/// ├── flow_noc
+/// ├── flow_occ
+/// ├── image_2
+/// ├── image_3
+/// └── obj_map
+/// ```
+#[derive(Debug, Clone)]
+pub struct StereoDataset {
+    dataset_dir: PathBuf,
+    frame_ids: Vec<usize>,
+    sub_dirs: BTreeMap<String, SubDir>,
+}
+
+#[derive(Debug, Clone)]
+struct SubDir {
+    kind: DataKind,
+    /// Scenes having the reference sample.
+    index: DirIndex,
+    /// Scenes having the next sample.
+    next_ids: BTreeSet<usize>,
+}
+
+impl StereoDataset {
+    pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::default())
+    }
+
+    /// Opens the dataset directory with a policy for sub-directories
+    /// containing different scenes.
+    pub fn open_with<P>(dir: P, policy: MismatchPolicy) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::new().with_mismatch_policy(policy))
+    }
+
+    /// Opens the dataset directory with options to resolve mismatched
+    /// scenes. Sub-directories are always scanned regardless of the
+    /// index mode, because the file names are not contiguous.
+    pub fn open_with_options<P>(dir: P, options: OpenOptions) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dataset_dir = dir.as_ref();
+
+        let sub_dirs: BTreeMap<String, SubDir> = super::list_dir_names(dataset_dir)?
+            .into_iter()
+            .filter_map(|file_name| {
+                let kind = if file_name.starts_with("image") || file_name.starts_with("colored") {
+                    DataKind::Image
+                } else if file_name.starts_with("disp") {
+                    DataKind::Disparity
+                } else if file_name.starts_with("flow") {
+                    DataKind::Flow
+                } else if file_name.starts_with("obj_map") {
+                    DataKind::ObjectMap
+                } else {
+                    return None;
+                };
+                Some((file_name, kind))
+            })
+            .map(|(file_name, kind)| -> Result<_, Error> {
+                let sub_dir = scan_scene_dir(&dataset_dir.join(&file_name), kind)?;
+                Ok((file_name, sub_dir))
+            })
+            .try_collect()?;
+
+        let frame_ids = super::resolve_frame_ids(
+            sub_dirs
+                .iter()
+                .map(|(key, sub_dir)| (key.as_str(), &sub_dir.index)),
+            options.mismatch_policy(),
+        )?;
+
+        Ok(Self {
+            dataset_dir: dataset_dir.to_owned(),
+            frame_ids,
+            sub_dirs,
+        })
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.frame_ids.len()
+    }
+
+    /// Gets the scene IDs in ascending order.
+    pub fn frame_ids(&self) -> &[usize] {
+        &self.frame_ids
+    }
+
+    pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'_>> {
+        self.frame_ids.iter().map(|&frame_id| Frame {
+            dataset: self,
+            frame_id,
+        })
+    }
+
+    /// Gets the frame at the position in [frame_ids()](Self::frame_ids).
+    pub fn frame(&self, frame_idx: usize) -> Option<Frame<'_>> {
+        let frame_id = *self.frame_ids.get(frame_idx)?;
+        Some(Frame {
+            dataset: self,
+            frame_id,
+        })
+    }
+
+    /// Gets the frame by the scene ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Frame<'_>> {
+        self.frame_ids.binary_search(&frame_id).ok()?;
+        Some(Frame {
+            dataset: self,
+            frame_id,
+        })
+    }
+
+    /// Iterates over sub-directory keys in sorted order.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, DataKind)> {
+        self.sub_dirs
+            .iter()
+            .map(|(s, sub_dir)| (s.as_str(), sub_dir.kind))
+    }
+
+    /// Gets the scene IDs, gaps and stray files of a sub-directory.
+    pub fn key_index(&self, key: &str) -> Option<&DirIndex> {
+        Some(&self.sub_dirs.get(key)?.index)
+    }
+
+    fn sample_path(&self, key: &str, frame_id: usize, step: TimeStep) -> PathBuf {
+        let suffix = match step {
+            TimeStep::Reference => 10,
+            TimeStep::Next => 11,
+        };
+        self.dataset_dir
+            .join(key)
+            .join(format!("{frame_id:06}_{suffix}.png"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame<'a> {
+    dataset: &'a StereoDataset,
+    frame_id: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Gets the scene ID.
+    pub fn id(&self) -> usize {
+        self.frame_id
+    }
+
+    /// Gets the reference sample `NNNNNN_10.png` of the key.
+    pub fn key(&self, key: &str) -> Option<Sample> {
+        self.key_at(key, TimeStep::Reference)
+    }
+
+    /// Gets the sample of the key at the time step. Only image keys
+    /// have samples at [TimeStep::Next].
+    pub fn key_at(&self, key: &str, step: TimeStep) -> Option<Sample> {
+        let sub_dir = self.dataset.sub_dirs.get(key)?;
+        let exists = match step {
+            TimeStep::Reference => sub_dir.index.contains(self.frame_id),
+            TimeStep::Next => sub_dir.next_ids.contains(&self.frame_id),
+        };
+        if !exists {
+            return None;
+        }
+
+        let path = self.dataset.sample_path(key, self.frame_id, step);
+        Some(Sample {
+            kind: sub_dir.kind,
+            path,
+        })
+    }
+
+    /// Iterates over the reference samples of all keys.
+    pub fn sample_iter(&self) -> impl Iterator<Item = Sample> + '_ {
+        self.dataset.sub_dirs.keys().filter_map(|key| self.key(key))
+    }
+}
+
+/// The time step of a sample in a scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeStep {
+    /// The reference frame `NNNNNN_10.png` where ground truth is
+    /// given.
+    Reference,
+    /// The next frame `NNNNNN_11.png`.
+    Next,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    Image,
+    Disparity,
+    Flow,
+    ObjectMap,
+}
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    kind: DataKind,
+    path: PathBuf,
+}
+
+impl Sample {
+    pub fn kind(&self) -> DataKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn data(&self) -> Result<SampleData, Error> {
+        SampleData::open(self.kind, &self.path)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SampleData {
+    Image(Box<DynamicImage>),
+    Disparity(Box<DisparityMap>),
+    Flow(Box<FlowField>),
+    /// The object map, where each pixel is the index of the object it
+    /// belongs to and zero is the background.
+    ObjectMap(Box<GrayImage>),
+}
+
+impl SampleData {
+    pub fn open<P>(kind: DataKind, path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let sample = match kind {
+            DataKind::Image => {
+                let image = super::open_image(path.as_ref())?;
+                SampleData::Image(Box::new(image))
+            }
+            DataKind::Disparity => {
+                let disp = DisparityMap::from_path(path)?;
+                SampleData::Disparity(Box::new(disp))
+            }
+            DataKind::Flow => {
+                let flow = FlowField::from_path(path)?;
+                SampleData::Flow(Box::new(flow))
+            }
+            DataKind::ObjectMap => {
+                let image = super::open_image(path.as_ref())?;
+                SampleData::ObjectMap(Box::new(image.into_luma8()))
+            }
+        };
+        Ok(sample)
+    }
+}
+
+/// Lists a directory of `NNNNNN_10.png` and `NNNNNN_11.png` files.
+fn scan_scene_dir(dir: &Path, kind: DataKind) -> Result<SubDir, Error> {
+    let mut ids = BTreeSet::new();
+    let mut next_ids = BTreeSet::new();
+    let mut stray_files = vec![];
+
+    for entry in dir.read_dir()? {
+        let path = entry?.path();
+        let parsed = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".png"))
+            .and_then(|stem| stem.split_once('_'))
+            .filter(|(id, _)| id.len() == 6 && id.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|(id, suffix)| Some((id.parse::<usize>().ok()?, suffix)));
+
+        match parsed {
+            Some((id, "10")) if path.is_file() => {
+                ids.insert(id);
+            }
+            Some((id, "11")) if path.is_file() => {
+                next_ids.insert(id);
+            }
+            _ => stray_files.push(path),
+        }
+    }
+    stray_files.sort();
+
+    Ok(SubDir {
+        kind,
+        index: DirIndex { ids, stray_files },
+        next_ids,
+    })
+}
@@ -1,30 +1,58 @@
 use crate::{
-    common::PointCloud,
+    common::{PointCloud, PointCloudView},
+    dataset::{DirIndex, IndexMode, MismatchPolicy, OpenOptions},
     tracking::{Calibration, Label, Oxts},
     Error,
 };
 use image::DynamicImage;
 use itertools::Itertools;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     path::{Path, PathBuf},
 };
 
 #[derive(Debug, Clone)]
 pub struct TrackingDataset {
     dataset_dir: PathBuf,
-    num_frames: usize,
-    sub_dirs: HashMap<String, DataKind>,
+    index_mode: IndexMode,
+    frame_ids: Vec<usize>,
+    sub_dirs: BTreeMap<String, SubDir>,
+}
+
+#[derive(Debug, Clone)]
+struct SubDir {
+    kind: DataKind,
+    index: DirIndex,
 }
 
 impl TrackingDataset {
+    /// Opens the dataset directory with default [OpenOptions]. The
+    /// frames are those available in all sub-directories.
     pub fn open<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::default())
+    }
+
+    /// Opens the dataset directory with a policy for sub-directories
+    /// containing different frames.
+    pub fn open_with<P>(dir: P, policy: MismatchPolicy) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_options(dir, OpenOptions::new().with_mismatch_policy(policy))
+    }
+
+    /// Opens the dataset directory with options to index
+    /// sub-directories and to resolve mismatched frames.
+    pub fn open_with_options<P>(dir: P, options: OpenOptions) -> Result<Self, Error>
     where
         P: AsRef<Path>,
     {
         let dataset_dir = dir.as_ref();
 
-        let sub_dirs: HashMap<String, DataKind> = dataset_dir
+        let sub_dirs: BTreeMap<String, SubDir> = dataset_dir
             .read_dir()?
             .map(|entry| -> Result<_, Error> {
                 macro_rules! skip {
@@ -61,127 +89,203 @@ impl TrackingDataset {
                     skip!();
                 };
 
-                Ok(Some((file_name.to_string(), kind)))
+                let index = DirIndex::open(&path, 4, kind.file_ext(), options.index_mode())?;
+                Ok(Some((file_name.to_string(), SubDir { kind, index })))
             })
             .flatten_ok()
             .try_collect()?;
 
-        let num_frames = match sub_dirs.iter().next() {
-            Some((key, kind)) => {
-                super::probe_max_frames(&dataset_dir.join(key), 4, kind.file_ext())
-            }
-            None => 0,
-        };
+        let frame_ids = super::resolve_frame_ids(
+            sub_dirs
+                .iter()
+                .map(|(key, sub_dir)| (key.as_str(), &sub_dir.index)),
+            options.mismatch_policy(),
+        )?;
 
         Ok(Self {
-            num_frames,
+            frame_ids,
             sub_dirs,
             dataset_dir: dataset_dir.to_owned(),
+            index_mode: options.index_mode(),
         })
     }
 
     pub fn num_frames(&self) -> usize {
-        self.num_frames
+        self.frame_ids.len()
+    }
+
+    /// Gets the KITTI frame IDs in ascending order.
+    pub fn frame_ids(&self) -> &[usize] {
+        &self.frame_ids
     }
 
     pub fn frame_iter(&self) -> impl Iterator<Item = Frame<'_>> {
-        (0..self.num_frames).map(|frame_idx| Frame {
+        self.frame_ids.iter().map(|&frame_id| Frame {
             dataset: self,
-            frame_idx,
+            frame_id,
         })
     }
 
+    /// Gets the frame at the position in [frame_ids()](Self::frame_ids).
+    /// The position is the frame ID if IDs are contiguous.
     pub fn frame(&self, frame_idx: usize) -> Option<Frame<'_>> {
-        if frame_idx >= self.num_frames {
-            return None;
-        }
+        let frame_id = *self.frame_ids.get(frame_idx)?;
+        Some(Frame {
+            dataset: self,
+            frame_id,
+        })
+    }
 
+    /// Gets the frame by its KITTI frame ID, which is the number in
+    /// the file names.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Frame<'_>> {
+        self.frame_ids.binary_search(&frame_id).ok()?;
         Some(Frame {
             dataset: self,
-            frame_idx,
+            frame_id,
         })
     }
 
     pub fn key(&self, key: &str) -> Option<KeyEntry<'_>> {
-        let kind = *self.sub_dirs.get(key)?;
+        let (key, sub_dir) = self.sub_dirs.get_key_value(key)?;
         Some(KeyEntry {
             dataset: self,
-            key: key.to_string(),
-            kind,
+            key,
+            sub_dir,
         })
     }
 
+    /// Iterates over sub-directory keys in sorted order.
     pub fn keys(&self) -> impl Iterator<Item = (&str, DataKind)> {
-        self.sub_dirs.iter().map(|(s, &k)| (s.as_str(), k))
+        self.sub_dirs
+            .iter()
+            .map(|(s, sub_dir)| (s.as_str(), sub_dir.kind))
+    }
+
+    /// Gets the number of frames found in each sub-directory in sorted
+    /// order of keys.
+    pub fn key_num_frames(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.sub_dirs
+            .iter()
+            .map(|(s, sub_dir)| (s.as_str(), sub_dir.index.len()))
+    }
+
+    /// Lists the sub-directories having the frame.
+    fn sub_dirs_at(&self, frame_id: usize) -> impl Iterator<Item = (&String, DataKind)> {
+        self.sub_dirs
+            .iter()
+            .filter(move |(_, sub_dir)| sub_dir.index.contains(frame_id))
+            .map(|(key, sub_dir)| (key, sub_dir.kind))
+    }
+
+    fn sample_path(&self, key: &str, kind: DataKind, frame_id: usize) -> PathBuf {
+        self.dataset_dir
+            .join(key)
+            .join(create_file_name(frame_id, kind.file_ext()))
+    }
+
+    fn sample(&self, kind: DataKind, path: PathBuf) -> Sample {
+        Sample {
+            kind,
+            path,
+            index_mode: self.index_mode,
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Frame<'a> {
     dataset: &'a TrackingDataset,
-    frame_idx: usize,
+    frame_id: usize,
 }
 
 impl<'a> Frame<'a> {
+    /// Gets the KITTI frame ID.
+    pub fn id(&self) -> usize {
+        self.frame_id
+    }
+
     pub fn key(&self, key: &str) -> Option<Sample> {
-        let kind = *self.dataset.sub_dirs.get(key)?;
-        let file_name = create_file_name(self.frame_idx, kind.file_ext());
-        let path = self.dataset.dataset_dir.join(key).join(file_name);
-        let sample = Sample { kind, path };
-        Some(sample)
+        let SubDir { kind, index } = self.dataset.sub_dirs.get(key)?;
+        if !index.contains(self.frame_id) {
+            return None;
+        }
+        let path = self.dataset.sample_path(key, *kind, self.frame_id);
+        Some(self.dataset.sample(*kind, path))
     }
 
     pub fn sample_iter(&self) -> impl Iterator<Item = Sample> + '_ {
-        self.dataset.sub_dirs.iter().map(|(key, &kind)| {
-            let file_name = create_file_name(self.frame_idx, kind.file_ext());
-            let path = self.dataset.dataset_dir.join(key).join(file_name);
-            Sample { kind, path }
+        self.dataset.sub_dirs_at(self.frame_id).map(|(key, kind)| {
+            let path = self.dataset.sample_path(key, kind, self.frame_id);
+            self.dataset.sample(kind, path)
         })
     }
 
+    /// Gets the number of frames in the image or Velodyne sequence of
+    /// the frame. It returns `None` if the frame has no sequence or
+    /// the sequence directory cannot be read.
     pub fn seq_len(&self) -> Option<usize> {
         let (key, kind) = self
             .dataset
-            .sub_dirs
-            .iter()
+            .sub_dirs_at(self.frame_id)
             .find(|(_, kind)| [DataKind::ImageSeq, DataKind::VelodyneSeq].contains(kind))?;
 
         let seq_dir = self
             .dataset
             .dataset_dir
             .join(key)
-            .join(format!("{:04}", self.frame_idx));
-        let ext = match kind {
-            DataKind::ImageSeq => "png",
-            DataKind::VelodyneSeq => "bin",
-            _ => unreachable!(),
-        };
-        let seq_len = super::probe_max_frames(&seq_dir, 6, Some(ext));
-        Some(seq_len)
+            .join(format!("{:04}", self.frame_id));
+        let index =
+            DirIndex::open(&seq_dir, 6, kind.seq_file_ext(), self.dataset.index_mode).ok()?;
+        Some(index.len())
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct KeyEntry<'a> {
     dataset: &'a TrackingDataset,
-    key: String,
-    kind: DataKind,
+    key: &'a str,
+    sub_dir: &'a SubDir,
 }
 
 impl<'a> KeyEntry<'a> {
+    pub fn kind(&self) -> DataKind {
+        self.sub_dir.kind
+    }
+
+    /// Gets the number of frames found in the sub-directory. It can
+    /// differ from the dataset frame count if sub-directories are
+    /// mismatched.
+    pub fn num_frames(&self) -> usize {
+        self.sub_dir.index.len()
+    }
+
+    /// Gets the frame IDs, gaps and stray files in the sub-directory.
+    pub fn index(&self) -> &'a DirIndex {
+        &self.sub_dir.index
+    }
+
+    /// Gets the sample of the frame at the position in
+    /// [TrackingDataset::frame_ids()].
     pub fn frame(&self, frame_idx: usize) -> Option<Sample> {
-        if frame_idx >= self.dataset.num_frames {
+        let frame_id = *self.dataset.frame_ids.get(frame_idx)?;
+        self.sample(frame_id)
+    }
+
+    /// Gets the sample by the KITTI frame ID.
+    pub fn frame_by_id(&self, frame_id: usize) -> Option<Sample> {
+        self.dataset.frame_ids.binary_search(&frame_id).ok()?;
+        self.sample(frame_id)
+    }
+
+    fn sample(&self, frame_id: usize) -> Option<Sample> {
+        if !self.sub_dir.index.contains(frame_id) {
             return None;
         }
 
-        let file_name = create_file_name(frame_idx, self.kind.file_ext());
-        let path = self.dataset.dataset_dir.join(&self.key).join(file_name);
-
-        let sample = Sample {
-            kind: self.kind,
-            path,
-        };
-        Some(sample)
+        let kind = self.sub_dir.kind;
+        let path = self.dataset.sample_path(self.key, kind, frame_id);
+        Some(self.dataset.sample(kind, path))
     }
 }
 
@@ -204,12 +308,22 @@ impl DataKind {
             DataKind::Odomentry => "txt",
         })
     }
+
+    /// Gets the file extension of the frames within a sequence.
+    fn seq_file_ext(&self) -> Option<&str> {
+        match self {
+            DataKind::ImageSeq => Some("png"),
+            DataKind::VelodyneSeq => Some("bin"),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Sample {
     kind: DataKind,
     path: PathBuf,
+    index_mode: IndexMode,
 }
 
 impl Sample {
@@ -222,7 +336,7 @@ impl Sample {
     }
 
     pub fn data(&self) -> Result<SampleData, Error> {
-        SampleData::open(self.kind, &self.path)
+        SampleData::open(self.kind, &self.path, self.index_mode)
     }
 }
 
@@ -236,27 +350,27 @@ pub enum SampleData {
 }
 
 impl SampleData {
-    fn open<P>(kind: DataKind, path: P) -> Result<SampleData, Error>
+    fn open<P>(kind: DataKind, path: P, index_mode: IndexMode) -> Result<SampleData, Error>
     where
         P: AsRef<Path>,
     {
         let sample = match kind {
             DataKind::ImageSeq => {
                 let dir = path.as_ref();
-                let num_files = super::probe_max_frames(dir, 6, Some("png"));
+                let index = DirIndex::open(dir, 6, kind.seq_file_ext(), index_mode)?;
 
                 SampleData::ImageSeq(ImageSeq {
                     dir: dir.to_owned(),
-                    seq_len: num_files,
+                    index,
                 })
             }
             DataKind::VelodyneSeq => {
                 let dir = path.as_ref();
-                let num_files = super::probe_max_frames(dir, 6, Some("bin"));
+                let index = DirIndex::open(dir, 6, kind.seq_file_ext(), index_mode)?;
 
                 SampleData::VelodyneSeq(VelodyneSeq {
                     dir: dir.to_owned(),
-                    seq_len: num_files,
+                    index,
                 })
             }
             DataKind::Calib => {
@@ -280,42 +394,48 @@ impl SampleData {
 #[derive(Debug, Clone)]
 pub struct ImageSeq {
     dir: PathBuf,
-    seq_len: usize,
+    index: DirIndex,
 }
 
 impl ImageSeq {
     pub fn get(&self, seq_idx: usize) -> Result<Option<DynamicImage>, Error> {
-        if seq_idx >= self.seq_len {
+        if !self.index.contains(seq_idx) {
             return Ok(None);
         }
 
         let path = self.dir.join(format!("{seq_idx:06}.png"));
-        let image = image::io::Reader::open(path)?.decode()?;
+        let image = super::open_image(&path)?;
         Ok(Some(image))
     }
 
     pub fn image_iter(&self) -> impl Iterator<Item = Result<DynamicImage, Error>> + '_ {
-        (0..self.seq_len).map(|seq_idx| {
+        self.index.ids().map(|seq_idx| {
             let path = self.dir.join(format!("{seq_idx:06}.png"));
-            let image = image::io::Reader::open(path)?.decode()?;
+            let image = super::open_image(&path)?;
             Ok(image)
         })
     }
 
     pub fn seq_len(&self) -> usize {
-        self.seq_len
+        self.index.len()
+    }
+
+    /// Gets the frame numbers, gaps and stray files in the sequence
+    /// directory.
+    pub fn index(&self) -> &DirIndex {
+        &self.index
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct VelodyneSeq {
     dir: PathBuf,
-    seq_len: usize,
+    index: DirIndex,
 }
 
 impl VelodyneSeq {
     pub fn get(&self, seq_idx: usize) -> Result<Option<PointCloud>, Error> {
-        if seq_idx >= self.seq_len {
+        if !self.index.contains(seq_idx) {
             return Ok(None);
         }
 
@@ -325,15 +445,41 @@ impl VelodyneSeq {
     }
 
     pub fn point_cloud_iter(&self) -> impl Iterator<Item = Result<PointCloud, Error>> + '_ {
-        (0..self.seq_len).map(|seq_idx| {
+        self.index.ids().map(|seq_idx| {
             let path = self.dir.join(format!("{seq_idx:06}.bin"));
             let image = PointCloud::from_path(path)?;
             Ok(image)
         })
     }
 
+    /// Memory-maps the point cloud file at the sequence index.
+    pub fn get_view(&self, seq_idx: usize) -> Result<Option<PointCloudView>, Error> {
+        if !self.index.contains(seq_idx) {
+            return Ok(None);
+        }
+
+        let path = self.dir.join(format!("{seq_idx:06}.bin"));
+        let view = PointCloudView::open(path)?;
+        Ok(Some(view))
+    }
+
+    pub fn point_cloud_view_iter(
+        &self,
+    ) -> impl Iterator<Item = Result<PointCloudView, Error>> + '_ {
+        self.index.ids().map(|seq_idx| {
+            let path = self.dir.join(format!("{seq_idx:06}.bin"));
+            PointCloudView::open(path)
+        })
+    }
+
     pub fn seq_len(&self) -> usize {
-        self.seq_len
+        self.index.len()
+    }
+
+    /// Gets the frame numbers, gaps and stray files in the sequence
+    /// directory.
+    pub fn index(&self) -> &DirIndex {
+        &self.index
     }
 }
 
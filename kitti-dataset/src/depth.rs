@@ -0,0 +1,6 @@
+mod depth_map;
+pub mod eval;
+mod intrinsics;
+
+pub use depth_map::{DepthCodec, DepthMap};
+pub use intrinsics::Intrinsics;
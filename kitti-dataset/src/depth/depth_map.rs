@@ -0,0 +1,117 @@
+use crate::common::{scaled_png, MapCodec, MaskedMap};
+use image::{DynamicImage, ImageBuffer, Luma};
+
+/// The scale of depth values stored in 16-bit PNG files.
+const DEPTH_SCALE: f32 = 256.0;
+
+/// A depth map with a validity mask, such as the sparse projected
+/// Velodyne input or the semi-dense ground truth of the depth
+/// benchmark.
+///
+/// KITTI stores depth maps in 16-bit grayscale PNG files, where a
+/// pixel value `v` encodes the depth `v / 256` in meters and zero
+/// marks a pixel without measurement. Negative and non-finite depths
+/// are invalid.
+///
+/// ```ignore
+/// let depth = DepthMap::from_path("proj_depth/groundtruth/image_02/0000000005.png")?;
+/// if let Some(d) = depth.get(100, 200) {
+///     println!("{d}");
+/// }
+/// ```
+pub type DepthMap = MaskedMap<DepthCodec>;
+
+/// The PNG encoding of [DepthMap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthCodec;
+
+impl MapCodec for DepthCodec {
+    type Value = f32;
+    type Pixel = Luma<u16>;
+
+    const FORMAT: &'static str = "16-bit grayscale depth";
+
+    fn as_image(image: &DynamicImage) -> Option<&ImageBuffer<Luma<u16>, Vec<u16>>> {
+        image.as_luma16()
+    }
+
+    fn decode(pixel: &Luma<u16>) -> Option<f32> {
+        scaled_png::decode(pixel, DEPTH_SCALE)
+    }
+
+    fn encode(value: Option<f32>) -> Luma<u16> {
+        scaled_png::encode(value, DEPTH_SCALE)
+    }
+
+    fn is_valid(value: f32) -> bool {
+        scaled_png::is_valid(value)
+    }
+}
+
+impl DepthMap {
+    /// Fills invalid pixels as the devkit `interpolateBackground()`
+    /// does before evaluation. Gaps within a row take the farther of
+    /// the two bounding depths, and the remaining pixels are
+    /// extrapolated from the nearest valid pixel, first along rows
+    /// and then along columns. A map without valid pixels is left
+    /// unchanged.
+    pub fn interpolate_background(&mut self) {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        let (values, valid) = self.parts_mut();
+
+        for row in 0..height {
+            let offset = row * width;
+            let values = &mut values[offset..offset + width];
+            let valid = &mut valid[offset..offset + width];
+
+            // Fill the gaps between valid pixels.
+            let mut count = 0;
+            for col in 0..width {
+                if !valid[col] {
+                    count += 1;
+                    continue;
+                }
+                if count > 0 && col > count {
+                    let first = col - count;
+                    let depth = values[first - 1].max(values[col]);
+                    values[first..col].fill(depth);
+                    valid[first..col].fill(true);
+                }
+                count = 0;
+            }
+
+            // Extrapolate to the left and to the right.
+            if let Some(first) = valid.iter().position(|&valid| valid) {
+                let depth = values[first];
+                values[..first].fill(depth);
+                valid[..first].fill(true);
+            }
+            if let Some(last) = valid.iter().rposition(|&valid| valid) {
+                let depth = values[last];
+                values[last + 1..].fill(depth);
+                valid[last + 1..].fill(true);
+            }
+        }
+
+        // Extrapolate to the top and to the bottom.
+        for col in 0..width {
+            let index = |row: usize| row * width + col;
+
+            if let Some(first) = (0..height).find(|&row| valid[index(row)]) {
+                let depth = values[index(first)];
+                for row in 0..first {
+                    values[index(row)] = depth;
+                    valid[index(row)] = true;
+                }
+            }
+            if let Some(last) = (0..height).rev().find(|&row| valid[index(row)]) {
+                let depth = values[index(last)];
+                for row in last + 1..height {
+                    values[index(row)] = depth;
+                    valid[index(row)] = true;
+                }
+            }
+        }
+    }
+}
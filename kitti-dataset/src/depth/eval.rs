@@ -0,0 +1,158 @@
+//! Depth evaluation compatible with the official KITTI depth
+//! completion and prediction devkit.
+//!
+//! The prediction is first filled by
+//! [DepthMap::interpolate_background]. The errors are computed over
+//! the pixels with valid ground truth, then averaged over images as
+//! the benchmark server does. The completion benchmark ranks by RMSE
+//! and the prediction benchmark ranks by SILog.
+//!
+//! ```ignore
+//! let result = evaluate(pairs)?;
+//! let metrics = result.metrics().unwrap();
+//! println!("{}mm {}1/km", metrics.rmse, metrics.irmse);
+//! ```
+
+use super::DepthMap;
+use crate::Error;
+use std::borrow::Borrow;
+
+/// The errors of one image or averaged over images, in the units of
+/// the leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// The scale invariant logarithmic error in log(m) * 100.
+    pub silog: f64,
+    /// The relative absolute error in percent.
+    pub abs_rel: f64,
+    /// The relative squared error in percent.
+    pub sq_rel: f64,
+    /// The mean absolute error in millimeters.
+    pub mae: f64,
+    /// The root mean squared error in millimeters.
+    pub rmse: f64,
+    /// The mean absolute error of the inverse depth in 1/km.
+    pub imae: f64,
+    /// The root mean squared error of the inverse depth in 1/km.
+    pub irmse: f64,
+}
+
+/// The evaluation result of multiple images.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalResult {
+    /// The errors of each image. It is `None` if the ground truth has
+    /// no valid pixels.
+    pub images: Vec<Option<Metrics>>,
+}
+
+impl EvalResult {
+    /// Gets the errors averaged over images, the numbers reported by
+    /// the benchmark. Images without valid ground truth are skipped.
+    pub fn metrics(&self) -> Option<Metrics> {
+        let images: Vec<&Metrics> = self.images.iter().flatten().collect();
+        if images.is_empty() {
+            return None;
+        }
+
+        let mean = |get: fn(&Metrics) -> f64| {
+            images.iter().map(|metrics| get(metrics)).sum::<f64>() / images.len() as f64
+        };
+        Some(Metrics {
+            silog: mean(|m| m.silog),
+            abs_rel: mean(|m| m.abs_rel),
+            sq_rel: mean(|m| m.sq_rel),
+            mae: mean(|m| m.mae),
+            rmse: mean(|m| m.rmse),
+            imae: mean(|m| m.imae),
+            irmse: mean(|m| m.irmse),
+        })
+    }
+}
+
+/// Evaluates predicted depth maps against ground truth depth maps.
+pub fn evaluate<I, G, P>(images: I) -> Result<EvalResult, Error>
+where
+    I: IntoIterator<Item = (G, P)>,
+    G: Borrow<DepthMap>,
+    P: Borrow<DepthMap>,
+{
+    let images: Vec<_> = images
+        .into_iter()
+        .map(|(gt, pred)| evaluate_image(gt.borrow(), pred.borrow()))
+        .collect::<Result<_, _>>()?;
+    Ok(EvalResult { images })
+}
+
+/// Evaluates a predicted depth map against the ground truth. Both
+/// must have the same size. Pixels with non-positive depths are
+/// skipped since the inverse and logarithmic errors are undefined. It
+/// returns `None` if no valid ground truth pixel is left, and fails
+/// with [Error::EmptyPrediction] if the prediction has no valid
+/// pixels to fill the map.
+pub fn evaluate_image(gt: &DepthMap, pred: &DepthMap) -> Result<Option<Metrics>, Error> {
+    let expect = (gt.width(), gt.height());
+    let found = (pred.width(), pred.height());
+    if expect != found {
+        return Err(Error::DepthSizeMismatch { expect, found });
+    }
+
+    let mut pred = pred.clone();
+    pred.interpolate_background();
+    if pred.num_valid() == 0 {
+        return Err(Error::EmptyPrediction);
+    }
+
+    let mut num_pixels = 0;
+    let mut abs_err = 0.0;
+    let mut sq_err = 0.0;
+    let mut abs_inv_err = 0.0;
+    let mut sq_inv_err = 0.0;
+    let mut log_diff = 0.0;
+    let mut sq_log_diff = 0.0;
+    let mut abs_rel = 0.0;
+    let mut sq_rel = 0.0;
+
+    let pixels = gt
+        .values()
+        .iter()
+        .zip(gt.valid_mask())
+        .zip(pred.values())
+        .filter(|((_, &valid), _)| valid)
+        .map(|((&gt, _), &pred)| (gt as f64, pred as f64))
+        .filter(|&(gt, pred)| gt > 0.0 && pred > 0.0);
+
+    for (gt, pred) in pixels {
+        let err = (gt - pred).abs();
+        let inv_err = (1.0 / gt - 1.0 / pred).abs();
+        let diff = pred.ln() - gt.ln();
+
+        num_pixels += 1;
+        abs_err += err;
+        sq_err += err * err;
+        abs_inv_err += inv_err;
+        sq_inv_err += inv_err * inv_err;
+        log_diff += diff;
+        sq_log_diff += diff * diff;
+        abs_rel += err / gt;
+        sq_rel += (err / gt).powi(2);
+    }
+
+    if num_pixels == 0 {
+        return Ok(None);
+    }
+    let num_pixels = num_pixels as f64;
+    let mean_log_diff = log_diff / num_pixels;
+
+    Ok(Some(Metrics {
+        silog: (sq_log_diff / num_pixels - mean_log_diff * mean_log_diff)
+            .max(0.0)
+            .sqrt()
+            * 100.0,
+        abs_rel: abs_rel / num_pixels * 100.0,
+        sq_rel: sq_rel / num_pixels * 100.0,
+        mae: abs_err / num_pixels * 1000.0,
+        rmse: (sq_err / num_pixels).sqrt() * 1000.0,
+        imae: abs_inv_err / num_pixels * 1000.0,
+        irmse: (sq_inv_err / num_pixels).sqrt() * 1000.0,
+    }))
+}
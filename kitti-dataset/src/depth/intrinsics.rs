@@ -0,0 +1,86 @@
+use crate::{float_format::format_exp, Error, Transform2D};
+use itertools::Itertools;
+use std::{
+    fs::File,
+    io::{self, prelude::*, BufWriter},
+    path::Path,
+    str::FromStr,
+};
+
+/// The 3x3 camera matrix of a depth benchmark image, which is stored
+/// in row-major order on one line.
+///
+/// ```text
+/// 7.215377e+02 0.000000e+00 5.966593e+02 0.000000e+00 7.215377e+02 1.498540e+02 0.000000e+00 0.000000e+00 1.000000e+00
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intrinsics(pub Transform2D);
+
+impl Intrinsics {
+    pub fn from_reader<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        text.parse()
+    }
+
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn write_to_writer<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let line = self
+            .0
+             .0
+            .iter()
+            .flatten()
+            .map(|&value| format_exp(value as f64, 6))
+            .join(" ");
+        writeln!(writer, "{line}")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_to_path<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let writer = BufWriter::new(File::create(path)?);
+        self.write_to_writer(writer)
+    }
+
+    pub fn write_to_string(&self) -> io::Result<String> {
+        let mut buf = vec![];
+        self.write_to_writer(&mut buf)?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+}
+
+impl FromStr for Intrinsics {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let values: Vec<f32> = text
+            .split_ascii_whitespace()
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|_| Error::InvalidIntrinsics(format!("invalid token \"{token}\"")))
+            })
+            .try_collect()?;
+        let found = values.len();
+        let values: [f32; 9] = values.try_into().map_err(|_| {
+            Error::InvalidIntrinsics(format!("expect 9 values, but get {found} values"))
+        })?;
+        let [a, b, c, d, e, f, g, h, i] = values;
+        Ok(Self(Transform2D([[a, b, c], [d, e, f], [g, h, i]])))
+    }
+}
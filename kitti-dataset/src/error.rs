@@ -1,4 +1,4 @@
-use std::{fmt, io};
+use std::{fmt, io, path::PathBuf};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -20,6 +20,58 @@ pub enum Error {
     #[error("invalid truncation value \"{0}\"")]
     InvalidTruncationValue(f64),
 
+    #[error("invalid token \"{token}\" in pose at line {line}")]
+    InvalidPoseToken { line: usize, token: String },
+
+    #[error("expect {expect} values in pose at line {line}, but get {found} values")]
+    InvalidPoseLength {
+        line: usize,
+        expect: usize,
+        found: usize,
+    },
+
+    #[error("expect {expect} estimated poses to match the ground truth, but get {found}")]
+    PoseCountMismatch { expect: usize, found: usize },
+
+    #[error("point cloud data size {0} is not a multiple of the 16-byte point size")]
+    TruncatedPointCloud(usize),
+
+    #[error("invalid timestamp \"{0}\"")]
+    InvalidTimestamp(String),
+
+    #[error("expect 15 or 16 fields in detection at line {line}, but get {found} fields")]
+    InvalidDetectionLength { line: u64, found: usize },
+
+    #[error("invalid detection score \"{0}\"")]
+    InvalidScore(String),
+
+    #[error("invalid frame ID \"{token}\" in split at line {line}")]
+    InvalidSplitId { line: usize, token: String },
+
+    #[error("sub-directories contain mismatched frames: {}", format_mismatches(.keys))]
+    FrameMismatch { keys: Vec<KeyMismatch> },
+
+    #[error("invalid intrinsics: {0}")]
+    InvalidIntrinsics(String),
+
+    #[error("missing file {0} paired with the depth sample")]
+    MissingPairedFile(PathBuf),
+
+    #[error(
+        "expect a {}x{} predicted depth map to match the ground truth, but get {}x{}",
+        .expect.0, .expect.1, .found.0, .found.1
+    )]
+    DepthSizeMismatch {
+        expect: (u32, u32),
+        found: (u32, u32),
+    },
+
+    #[error("the predicted depth map has no valid pixels")]
+    EmptyPrediction,
+
+    #[error("invalid image format: {0}")]
+    InvalidImageFormat(String),
+
     #[error("image error: {0}")]
     ImageError(image::error::ImageError),
 }
@@ -47,3 +99,29 @@ impl From<image::error::ImageError> for Error {
         Self::ImageError(v)
     }
 }
+
+/// The frames of a sub-directory compared to the other
+/// sub-directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMismatch {
+    pub key: String,
+    pub num_frames: usize,
+    /// The number of frame IDs found in other sub-directories but not
+    /// in this one.
+    pub num_missing: usize,
+    /// The smallest missing frame ID.
+    pub first_missing: Option<usize>,
+}
+
+fn format_mismatches(keys: &[KeyMismatch]) -> String {
+    keys.iter()
+        .map(|key| match key.first_missing {
+            Some(first) => format!(
+                "{} ({} frames, {} missing, first missing ID {first})",
+                key.key, key.num_frames, key.num_missing
+            ),
+            None => format!("{} ({} frames)", key.key, key.num_frames),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
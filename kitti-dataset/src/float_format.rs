@@ -0,0 +1,51 @@
+//! Float formatting compatible with C's printf() conversions.
+
+/// Formats the value like `%.{precision}e` in C.
+pub fn format_exp(value: f64, precision: usize) -> String {
+    let text = format!("{value:.precision$e}");
+    let Some((mantissa, exp)) = text.split_once('e') else {
+        // inf or NaN
+        return text;
+    };
+    let exp: i32 = exp.parse().unwrap();
+    let sign = if exp < 0 { '-' } else { '+' };
+    format!("{mantissa}e{sign}{:02}", exp.abs())
+}
+
+/// Formats the value like `%.{precision}g` in C.
+pub fn format_general(value: f64, precision: usize) -> String {
+    if !value.is_finite() {
+        return if value.is_nan() {
+            "nan".to_string()
+        } else if value > 0.0 {
+            "inf".to_string()
+        } else {
+            "-inf".to_string()
+        };
+    }
+
+    let precision = precision.max(1);
+
+    // Round to the significant digits first to find the exponent of
+    // the rounded value.
+    let text = format!("{value:.prec$e}", prec = precision - 1);
+    let (_, exp) = text.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+
+    if exp < -4 || exp >= precision as i32 {
+        let text = format_exp(value, precision - 1);
+        let (mantissa, exp) = text.split_once('e').unwrap();
+        format!("{}e{exp}", strip_trailing_zeros(mantissa))
+    } else {
+        let frac_digits = (precision as i32 - 1 - exp) as usize;
+        strip_trailing_zeros(&format!("{value:.frac_digits$}")).to_string()
+    }
+}
+
+fn strip_trailing_zeros(text: &str) -> &str {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text
+    }
+}
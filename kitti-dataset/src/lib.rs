@@ -3,8 +3,12 @@
 //! ## Dataset Loader
 //!
 //! The dataset loader allows you to iterate through all kinds of data
-//! samples. Currently, [ObjectDataset](dataset::ObjectDataset) and
-//! [TrackingDataset](dataset::TrackingDataset) are supported.
+//! samples. Currently, [ObjectDataset](dataset::ObjectDataset),
+//! [TrackingDataset](dataset::TrackingDataset),
+//! [OdometryDataset](dataset::OdometryDataset),
+//! [RawDataset](dataset::RawDataset),
+//! [StereoDataset](dataset::StereoDataset) and
+//! [DepthDataset](dataset::DepthDataset) are supported.
 //!
 //! The dataset layout for _Object Detection Evaluation 2012_ dataset
 //! is presented below for example. You can download appropriate zip
@@ -61,8 +65,24 @@
 //! ### Common
 //!
 //! - [common::PointCloud] - 3D Object Detection Evaluation 2017 Velodyne point cloud type
+//! - [common::PointCloudView] - Zero-copy memory-mapped view on a Velodyne point cloud file
 //! - [common::ProjectionMatrix] - A 3x4 matrix that describes a world to camera coordinate transform
 //! - [common::Transform2D] - A 3x3 matrix that describes a 2D coordinate transform
+//! - [common::Projector] - Projects Velodyne points and 3D label boxes onto camera images
+//! - [common::MaskedMap] - Dense maps with a validity mask in 16-bit PNG files, such as depth, disparity and flow maps
+//! - [common::Timestamp] - Raw data `timestamps.txt` timestamps
+//!
+//!     ```text
+//!     2011-09-26 13:02:25.964389445
+//!     2011-09-26 13:02:26.068099975
+//!     ```
+//!
+//! - [common::TimeOffset] - Visual Odometry / SLAM Evaluation 2012 `times.txt` frame times
+//!
+//!     ```text
+//!     0.000000e+00
+//!     1.036224e-01
+//!     ```
 //!
 //!
 //! ### Object Detection
@@ -87,6 +107,20 @@
 //!     Tr_imu_to_velo: 9.999976000000e-01 7.553071000000e-04 -2.035826000000e-03 -8.086759000000e-01 -7.854027000000e-04 9.998898000000e-01 -1.482298000000e-02 3.195559000000e-01 2.024406000000e-03 1.482454000000e-02 9.998881000000e-01 -7.997231000000e-01
 //!     ```
 //!
+//! - [object::Detection] - Detector outputs with confidence scores, scored by [object::eval]
+//!
+//!     ```text
+//!     Car -1 -1 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59 0.93
+//!     ```
+//!
+//! - [object::Split] - `ImageSets` frame ID lists such as `train.txt` and `val.txt`
+//!
+//!     ```text
+//!     000000
+//!     000003
+//!     000007
+//!     ```
+//!
 //!
 //! ### Tracking
 //!
@@ -100,6 +134,10 @@
 //!     2 0 Car 0 0 2.952925 0.000000 181.644428 160.336384 258.017227 1.491984 1.588650 4.106539 -12.245073 1.702574 15.415174 2.289919
 //!     ```
 //!
+//! - [tracking::TrackSet] - Tracking labels grouped by track and by frame
+//!
+//! - [tracking::eval] - CLEAR MOT and HOTA evaluation of tracker outputs in the label format
+//!
 //! - [tracking::Oxts] - GPS/IMU data type for Object Tracking Evaluation
 //!
 //!     ```text
@@ -108,6 +146,8 @@
 //!     49.011207340729 8.4228879062526 112.86553955078 0.023136 0.014862 -1.1936236732051 -3.2411567408588 1.2346955705869 3.468542331508 0.020905692383421 0.087430817119982 -0.37599422874402 0.92464351685799 10.21491279541 -0.28214813630003 0.68838604691212 10.237591960914 -0.0011286126703085 0.10138706616598 0.12365328181544 8.8704809942824e-06 0.098502901229822 0.12597678052672 0.51960850647386 0.072917761896537 4 10 4 4 0
 //!     ```
 //!
+//! - [Oxts::poses](tracking::Oxts::poses) - Ego poses converted from GPS/IMU packets
+//!
 //!
 //! ### Odometry
 //!
@@ -128,14 +168,75 @@
 //!     9.999978e-01 5.272628e-04 -2.066935e-03 -4.690294e-02 -5.296506e-04 9.999992e-01 -1.154865e-03 -2.839928e-02 2.066324e-03 1.155958e-03 9.999971e-01 8.586941e-01
 //!     9.999910e-01 1.048972e-03 -4.131348e-03 -9.374345e-02 -1.058514e-03 9.999968e-01 -2.308104e-03 -5.676064e-02 4.128913e-03 2.312456e-03 9.999887e-01 1.716275e+00
 //!     ```
+//!
+//! - [odometry::eval] - Translational and rotational drift, ATE and RPE of estimated poses
+//!
+//!
+//! ### Stereo and Scene Flow
+//!
+//! - [stereo::DisparityMap] - Stereo 2012/2015 disparity maps in 16-bit PNG files
+//! - [stereo::FlowField] - Optical flow 2012/2015 flow fields in 16-bit RGB PNG files
+//!
+//!
+//! ### Depth
+//!
+//! - [depth::DepthMap] - Sparse Velodyne and ground truth depth maps in 16-bit PNG files
+//! - [depth::eval] - Depth completion and prediction errors of predicted depth maps
+//! - [depth::Intrinsics] - Depth benchmark camera matrices in `intrinsics/*.txt`
+//!
+//!     ```text
+//!     7.215377e+02 0.000000e+00 5.966593e+02 0.000000e+00 7.215377e+02 1.498540e+02 0.000000e+00 0.000000e+00 1.000000e+00
+//!     ```
+//!
+//!
+//! ### Raw Data
+//!
+//! - [raw::CamToCamCalibration] - Raw data camera intrinsics and rectification in `calib_cam_to_cam.txt`
+//!
+//!     ```text
+//!     calib_time: 09-Jan-2012 13:57:47
+//!     corner_dist: 9.950000e-02
+//!     S_00: 1.392000e+03 5.120000e+02
+//!     K_00: 9.842439e+02 0.000000e+00 6.900000e+02 0.000000e+00 9.808141e+02 2.331966e+02 0.000000e+00 0.000000e+00 1.000000e+00
+//!     D_00: -3.728755e-01 2.037299e-01 2.219027e-03 1.383707e-03 -7.233722e-02
+//!     R_00: 1.000000e+00 0.000000e+00 0.000000e+00 0.000000e+00 1.000000e+00 0.000000e+00 0.000000e+00 0.000000e+00 1.000000e+00
+//!     T_00: 2.573699e-16 -1.059758e-16 1.614870e-16
+//!     S_rect_00: 1.242000e+03 3.750000e+02
+//!     R_rect_00: 9.999239e-01 9.837760e-03 -7.445048e-03 -9.869795e-03 9.999421e-01 -4.278459e-03 7.402527e-03 4.351614e-03 9.999631e-01
+//!     P_rect_00: 7.215377e+02 0.000000e+00 6.095593e+02 0.000000e+00 0.000000e+00 7.215377e+02 1.728540e+02 0.000000e+00 0.000000e+00 0.000000e+00 1.000000e+00 0.000000e+00
+//!     ...
+//!     ```
+//!
+//! - [raw::VeloToCamCalibration] - Raw data Velodyne to camera transform in `calib_velo_to_cam.txt`
+//!
+//!     ```text
+//!     calib_time: 15-Mar-2012 11:37:16
+//!     R: 7.533745e-03 -9.999714e-01 -6.166020e-04 1.480249e-02 7.280733e-04 -9.998902e-01 9.998621e-01 7.523790e-03 1.480755e-02
+//!     T: -4.069766e-03 -7.631618e-02 -2.717806e-01
+//!     delta_f: 0.000000e+00 0.000000e+00
+//!     delta_c: 0.000000e+00 0.000000e+00
+//!     ```
+//!
+//! - [raw::ImuToVeloCalibration] - Raw data IMU to Velodyne transform in `calib_imu_to_velo.txt`
+//!
+//!     ```text
+//!     calib_time: 25-May-2012 16:47:16
+//!     R: 9.999976e-01 7.553071e-04 -2.035826e-03 -7.854027e-04 9.998898e-01 -1.482298e-02 2.024406e-03 1.482454e-02 9.998881e-01
+//!     T: -8.086759e-01 3.195559e-01 -7.997231e-01
+//!     ```
 
+pub(crate) mod assignment;
 pub(crate) mod calib_codegen;
 pub mod common;
 pub mod dataset;
+pub mod depth;
 pub mod error;
+pub(crate) mod float_format;
 pub mod object;
 pub mod odometry;
+pub mod raw;
 pub(crate) mod serde;
+pub mod stereo;
 pub mod tracking;
 
 pub use common::{Point, ProjectionMatrix, Transform2D};
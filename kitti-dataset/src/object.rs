@@ -1,4 +1,12 @@
+mod detection;
+pub mod eval;
+pub(crate) mod geometry;
+pub mod iou;
 mod label;
+mod split;
 
 pub use crate::calib_codegen::ObjectCalibration as Calibration;
+pub use detection::Detection;
+pub use geometry::{BOX_EDGES, BOX_FACES};
 pub use label::*;
+pub use split::Split;
@@ -0,0 +1,161 @@
+use super::{eval::ScoredLabel, Label};
+use crate::{common::indexed_files, Error};
+use itertools::Itertools;
+use std::{
+    borrow::Borrow,
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Cursor, Read, Write},
+    path::Path,
+};
+
+/// The number of fields in a ground truth label line.
+const NUM_LABEL_FIELDS: usize = 15;
+
+/// A detector output, which is a label with a confidence score.
+///
+/// Result files follow the label format with an additional score
+/// column. Lines without the score column, such as ground truth
+/// labels, get a score of 1.0.
+///
+/// ```text
+/// Car -1 -1 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59 0.93
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub label: Label,
+    pub score: f64,
+}
+
+impl Detection {
+    pub fn iter_from_reader<R>(reader: R) -> impl Iterator<Item = Result<Detection, Error>>
+    where
+        R: Read,
+    {
+        let reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(b' ')
+            .flexible(true)
+            .from_reader(reader);
+
+        reader.into_records().map(|record| {
+            let record = record?;
+            Self::from_record(&record)
+        })
+    }
+
+    pub fn iter_from_path<P>(path: P) -> io::Result<impl Iterator<Item = Result<Detection, Error>>>
+    where
+        P: AsRef<Path>,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(Self::iter_from_reader(reader))
+    }
+
+    pub fn iter_from_str(text: &str) -> impl Iterator<Item = Result<Detection, Error>> + '_ {
+        let reader = Cursor::new(text);
+        Self::iter_from_reader(reader)
+    }
+
+    pub fn vec_from_reader<R>(reader: R) -> Result<Vec<Detection>, Error>
+    where
+        R: Read,
+    {
+        Self::iter_from_reader(reader).try_collect()
+    }
+
+    pub fn vec_from_path<P>(path: P) -> Result<Vec<Detection>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::iter_from_path(path)?.try_collect()
+    }
+
+    pub fn vec_from_str(text: &str) -> Result<Vec<Detection>, Error> {
+        Self::iter_from_str(text).try_collect()
+    }
+
+    /// Loads all `NNNNNN.txt` result files in a directory, keyed by
+    /// the frame index. Other files are ignored.
+    pub fn map_from_dir<P>(dir: P) -> Result<BTreeMap<usize, Vec<Detection>>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        indexed_files::list_indexed_files(dir.as_ref(), "txt")?
+            .into_iter()
+            .map(|(frame_idx, path)| Ok((frame_idx, Self::vec_from_path(path)?)))
+            .collect()
+    }
+
+    pub fn write_to_writer<W, I, A>(writer: W, detections: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        W: Write,
+        A: Borrow<Detection>,
+    {
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .delimiter(b' ')
+            .from_writer(writer);
+
+        for detection in detections {
+            let Detection { label, score } = detection.borrow();
+            writer.serialize((label, score))?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn write_to_path<P, I, A>(path: P, detections: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        P: AsRef<Path>,
+        A: Borrow<Detection>,
+    {
+        let writer = BufWriter::new(File::create(path)?);
+        Self::write_to_writer(writer, detections)
+    }
+
+    pub fn write_to_string<I, A>(detections: I) -> io::Result<String>
+    where
+        I: IntoIterator<Item = A>,
+        A: Borrow<Detection>,
+    {
+        let mut buf = vec![];
+        Self::write_to_writer(&mut buf, detections)?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+
+    fn from_record(record: &csv::StringRecord) -> Result<Self, Error> {
+        let score = match record.len() {
+            NUM_LABEL_FIELDS => 1.0,
+            len if len == NUM_LABEL_FIELDS + 1 => {
+                let text = &record[NUM_LABEL_FIELDS];
+                text.parse()
+                    .ok()
+                    .filter(|score: &f64| score.is_finite())
+                    .ok_or_else(|| Error::InvalidScore(text.to_string()))?
+            }
+            found => {
+                let line = record.position().map(|pos| pos.line()).unwrap_or(0);
+                return Err(Error::InvalidDetectionLength { line, found });
+            }
+        };
+
+        // The label deserializer ignores the trailing score field.
+        let label: Label = record.deserialize(None)?;
+        Ok(Self { label, score })
+    }
+}
+
+impl ScoredLabel for Detection {
+    fn label(&self) -> &Label {
+        &self.label
+    }
+
+    fn score(&self) -> f64 {
+        self.score
+    }
+}
@@ -0,0 +1,586 @@
+//! Object detection evaluation compatible with the official KITTI
+//! `evaluate_object` devkit.
+//!
+//! The [evaluate()] function scores detections against ground truth
+//! labels frame by frame. A detection is any [ScoredLabel], such as a
+//! `(Label, score)` pair. It reports the precision-recall curves and
+//! the average precision (AP) of the 2D box, the average orientation
+//! similarity (AOS), the bird's eye view (BEV) box and the 3D box for
+//! the car, pedestrian and cyclist classes.
+//!
+//! ```ignore
+//! let frames = vec![(ground_truth, detections)];
+//! let result = evaluate(&frames);
+//! let car = result.class(&Class::Car).unwrap();
+//! println!("{}", car.box3d.ap40(Difficulty::Moderate));
+//! ```
+
+use super::{
+    iou::{bbox_ioa, bbox_iou, bev_iou, box3d_iou},
+    BoundingBox, Class, Label,
+};
+
+/// The number of recall positions on a precision-recall curve.
+pub const NUM_SAMPLE_POINTS: usize = 41;
+
+/// The classes evaluated by the devkit.
+pub const EVAL_CLASSES: [Class; 3] = [Class::Car, Class::Pedestrian, Class::Cyclist];
+
+/// The alpha value written by detectors that do not estimate the
+/// observation angle.
+const INVALID_ALPHA: f64 = -10.0;
+
+const NO_DETECTION: f64 = -10000000.0;
+
+/// The difficulty level that filters ground truth by box height,
+/// occlusion and truncation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy = 0,
+    Moderate = 1,
+    Hard = 2,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Moderate, Difficulty::Hard];
+
+    /// The minimum 2D box height in pixels.
+    pub fn min_height(&self) -> f64 {
+        [40.0, 25.0, 25.0][*self as usize]
+    }
+
+    /// The maximum occlusion level.
+    pub fn max_occlusion(&self) -> i32 {
+        [0, 1, 2][*self as usize]
+    }
+
+    /// The maximum truncation ratio.
+    pub fn max_truncation(&self) -> f64 {
+        [0.15, 0.3, 0.5][*self as usize]
+    }
+}
+
+/// The overlap measure used to match detections to ground truth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// The 2D box in the image.
+    Bbox = 0,
+    /// The 3D box footprint on the ground plane.
+    Bev = 1,
+    /// The 3D box.
+    Box3D = 2,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Bbox, Metric::Bev, Metric::Box3D];
+
+    /// The minimum overlap for a detection to match the ground truth
+    /// of the class.
+    pub fn min_overlap(&self, class: &Class) -> f64 {
+        match class {
+            Class::Car => 0.7,
+            _ => 0.5,
+        }
+    }
+
+    fn overlap(&self, det: &Label, gt: &Label) -> f64 {
+        match self {
+            Metric::Bbox => bbox_iou(&det.bbox, &gt.bbox),
+            Metric::Bev => bev_iou(det, gt),
+            Metric::Box3D => box3d_iou(det, gt),
+        }
+    }
+}
+
+/// The interpolated precision sampled at [NUM_SAMPLE_POINTS] recall
+/// thresholds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecisionCurve {
+    pub precision: [f64; NUM_SAMPLE_POINTS],
+}
+
+impl PrecisionCurve {
+    /// Computes the average precision in percent over 11 recall
+    /// positions, the metric used before October 2019.
+    pub fn ap11(&self) -> f64 {
+        let sum: f64 = self.precision.iter().step_by(4).sum();
+        sum / 11.0 * 100.0
+    }
+
+    /// Computes the average precision in percent over 40 recall
+    /// positions, excluding the zero recall.
+    pub fn ap40(&self) -> f64 {
+        let sum: f64 = self.precision[1..].iter().sum();
+        sum / 40.0 * 100.0
+    }
+}
+
+/// The precision curves of the easy, moderate and hard difficulties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curves(pub [PrecisionCurve; 3]);
+
+impl Curves {
+    pub fn get(&self, difficulty: Difficulty) -> &PrecisionCurve {
+        &self.0[difficulty as usize]
+    }
+
+    pub fn ap11(&self, difficulty: Difficulty) -> f64 {
+        self.get(difficulty).ap11()
+    }
+
+    pub fn ap40(&self, difficulty: Difficulty) -> f64 {
+        self.get(difficulty).ap40()
+    }
+}
+
+/// The evaluation result of one class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassResult {
+    pub class: Class,
+    pub bbox: Curves,
+    /// The orientation similarity curves. It is `None` if any
+    /// detection has an invalid alpha of -10.
+    pub aos: Option<Curves>,
+    pub bev: Curves,
+    pub box3d: Curves,
+}
+
+impl ClassResult {
+    pub fn metric(&self, metric: Metric) -> &Curves {
+        match metric {
+            Metric::Bbox => &self.bbox,
+            Metric::Bev => &self.bev,
+            Metric::Box3D => &self.box3d,
+        }
+    }
+}
+
+/// The evaluation result of all classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalResult {
+    /// The results of [EVAL_CLASSES]. Like the devkit, classes
+    /// without detections are skipped.
+    pub classes: Vec<ClassResult>,
+}
+
+impl EvalResult {
+    pub fn class(&self, class: &Class) -> Option<&ClassResult> {
+        self.classes.iter().find(|result| &result.class == class)
+    }
+}
+
+/// Evaluates detections against the ground truth. Each item is a
+/// pair of the ground truth labels and detections of one frame.
+///
+/// Like the devkit, the recall is sampled at the scores of true
+/// positives. The curve is truncated if there are fewer than
+/// [NUM_SAMPLE_POINTS] ground truth boxes.
+pub fn evaluate<I, G, D, T>(frames: I) -> EvalResult
+where
+    I: IntoIterator<Item = (G, D)>,
+    G: AsRef<[Label]>,
+    D: AsRef<[T]>,
+    T: ScoredLabel,
+{
+    let frames: Vec<(G, D)> = frames.into_iter().collect();
+    let frames: Vec<Frame> = frames
+        .iter()
+        .map(|(gt, det)| Frame::new(gt.as_ref(), det.as_ref()))
+        .collect();
+
+    let compute_aos = frames
+        .iter()
+        .flat_map(|frame| &frame.det)
+        .all(|det| det.label.alpha.as_radians() != INVALID_ALPHA);
+
+    let classes = EVAL_CLASSES
+        .iter()
+        .filter(|class| {
+            frames
+                .iter()
+                .flat_map(|frame| &frame.det)
+                .any(|det| &det.label.class == *class)
+        })
+        .map(|class| {
+            let eval = |metric: Metric, compute_aos: bool| {
+                let curves = Difficulty::ALL
+                    .map(|difficulty| eval_class(&frames, class, metric, difficulty, compute_aos));
+                let precision = Curves(curves.clone().map(|(precision, _)| precision));
+                let aos = curves
+                    .into_iter()
+                    .map(|(_, aos)| aos)
+                    .collect::<Option<Vec<_>>>()
+                    .map(|aos| Curves(aos.try_into().unwrap()));
+                (precision, aos)
+            };
+
+            let (bbox, aos) = eval(Metric::Bbox, compute_aos);
+            let (bev, _) = eval(Metric::Bev, false);
+            let (box3d, _) = eval(Metric::Box3D, false);
+
+            ClassResult {
+                class: class.clone(),
+                bbox,
+                aos,
+                bev,
+                box3d,
+            }
+        })
+        .collect();
+
+    EvalResult { classes }
+}
+
+/// A detection to evaluate, which is a label with a confidence score.
+pub trait ScoredLabel {
+    fn label(&self) -> &Label;
+
+    fn score(&self) -> f64;
+}
+
+impl ScoredLabel for (Label, f64) {
+    fn label(&self) -> &Label {
+        &self.0
+    }
+
+    fn score(&self) -> f64 {
+        self.1
+    }
+}
+
+/// A detection borrowed from the input.
+struct Scored<'a> {
+    label: &'a Label,
+    score: f64,
+}
+
+/// A frame with precomputed overlaps.
+struct Frame<'a> {
+    gt: &'a [Label],
+    det: Vec<Scored<'a>>,
+    dont_care: Vec<&'a BoundingBox>,
+    /// The detection-by-ground-truth overlap matrices for each metric.
+    overlaps: [Vec<Vec<f64>>; 3],
+}
+
+impl<'a> Frame<'a> {
+    fn new<T>(gt: &'a [Label], det: &'a [T]) -> Self
+    where
+        T: ScoredLabel,
+    {
+        let det: Vec<Scored> = det
+            .iter()
+            .map(|det| Scored {
+                label: det.label(),
+                score: det.score(),
+            })
+            .collect();
+        let dont_care = gt
+            .iter()
+            .filter(|label| label.class == Class::DontCare)
+            .map(|label| &label.bbox)
+            .collect();
+        let overlaps = Metric::ALL.map(|metric| {
+            det.iter()
+                .map(|det| gt.iter().map(|gt| metric.overlap(det.label, gt)).collect())
+                .collect()
+        });
+
+        Self {
+            gt,
+            det,
+            dont_care,
+            overlaps,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ignore {
+    /// Evaluated.
+    No,
+    /// Matched but not counted, such as a neighboring class or a box
+    /// out of the difficulty.
+    Yes,
+    /// Not related to the class.
+    Other,
+}
+
+struct CleanedFrame {
+    ignored_gt: Vec<Ignore>,
+    ignored_det: Vec<Ignore>,
+    num_gt: usize,
+}
+
+#[derive(Default)]
+struct Statistics {
+    tp: usize,
+    fp: usize,
+    fn_: usize,
+    similarity: Option<f64>,
+    scores: Vec<f64>,
+}
+
+fn eval_class(
+    frames: &[Frame],
+    class: &Class,
+    metric: Metric,
+    difficulty: Difficulty,
+    compute_aos: bool,
+) -> (PrecisionCurve, Option<PrecisionCurve>) {
+    let cleaned: Vec<CleanedFrame> = frames
+        .iter()
+        .map(|frame| clean_data(frame, class, difficulty))
+        .collect();
+    let num_gt: usize = cleaned.iter().map(|cleaned| cleaned.num_gt).sum();
+
+    // Collect the scores of true positives to find the thresholds.
+    let scores: Vec<f64> = frames
+        .iter()
+        .zip(&cleaned)
+        .flat_map(|(frame, cleaned)| {
+            compute_statistics(frame, cleaned, class, metric, false, false, 0.0).scores
+        })
+        .collect();
+    let thresholds = get_thresholds(scores, num_gt);
+
+    let mut tp = vec![0; thresholds.len()];
+    let mut fp = vec![0; thresholds.len()];
+    let mut similarity = vec![0.0; thresholds.len()];
+
+    for (frame, cleaned) in frames.iter().zip(&cleaned) {
+        for (idx, &thresh) in thresholds.iter().enumerate() {
+            let stats =
+                compute_statistics(frame, cleaned, class, metric, true, compute_aos, thresh);
+            tp[idx] += stats.tp;
+            fp[idx] += stats.fp;
+            if let Some(value) = stats.similarity {
+                similarity[idx] += value;
+            }
+        }
+    }
+
+    let mut precision = [0.0; NUM_SAMPLE_POINTS];
+    let mut aos = [0.0; NUM_SAMPLE_POINTS];
+    for idx in 0..thresholds.len() {
+        let total = (tp[idx] + fp[idx]) as f64;
+        precision[idx] = tp[idx] as f64 / total;
+        aos[idx] = similarity[idx] / total;
+    }
+
+    // Interpolate the precision by the maximum precision at larger
+    // recalls.
+    for idx in (0..NUM_SAMPLE_POINTS - 1).rev() {
+        precision[idx] = precision[idx].max(precision[idx + 1]);
+        aos[idx] = aos[idx].max(aos[idx + 1]);
+    }
+
+    let precision = PrecisionCurve { precision };
+    let aos = compute_aos.then_some(PrecisionCurve { precision: aos });
+    (precision, aos)
+}
+
+fn clean_data(frame: &Frame, class: &Class, difficulty: Difficulty) -> CleanedFrame {
+    let mut num_gt = 0;
+
+    let ignored_gt = frame
+        .gt
+        .iter()
+        .map(|gt| {
+            let is_class = &gt.class == class;
+            let is_neighbor = matches!(
+                (class, &gt.class),
+                (Class::Pedestrian, Class::PersonSitting) | (Class::Car, Class::Van)
+            );
+
+            let height = (gt.bbox.ymax - gt.bbox.ymin).abs();
+            let occlusion = gt.occlusion.map(|occ| occ as i32).unwrap_or(-1);
+            let truncation = gt.truncation.map(|trunc| trunc.as_f64()).unwrap_or(-1.0);
+            let ignore = occlusion > difficulty.max_occlusion()
+                || truncation > difficulty.max_truncation()
+                || height <= difficulty.min_height();
+
+            if is_class && !ignore {
+                num_gt += 1;
+                Ignore::No
+            } else if is_neighbor || is_class {
+                Ignore::Yes
+            } else {
+                Ignore::Other
+            }
+        })
+        .collect();
+
+    let ignored_det = frame
+        .det
+        .iter()
+        .map(|det| {
+            // The devkit truncates the detection height to an integer.
+            let height = (det.label.bbox.ymax - det.label.bbox.ymin).abs().trunc();
+
+            if height < difficulty.min_height() {
+                Ignore::Yes
+            } else if &det.label.class == class {
+                Ignore::No
+            } else {
+                Ignore::Other
+            }
+        })
+        .collect();
+
+    CleanedFrame {
+        ignored_gt,
+        ignored_det,
+        num_gt,
+    }
+}
+
+fn compute_statistics(
+    frame: &Frame,
+    cleaned: &CleanedFrame,
+    class: &Class,
+    metric: Metric,
+    compute_fp: bool,
+    compute_aos: bool,
+    thresh: f64,
+) -> Statistics {
+    let CleanedFrame {
+        ignored_gt,
+        ignored_det,
+        ..
+    } = cleaned;
+    let min_overlap = metric.min_overlap(class);
+    let overlaps = &frame.overlaps[metric as usize];
+
+    let mut stats = Statistics::default();
+    let mut deltas = vec![];
+    let mut assigned = vec![false; frame.det.len()];
+    let below_thresh: Vec<bool> = frame
+        .det
+        .iter()
+        .map(|det| compute_fp && det.score < thresh)
+        .collect();
+
+    for (gt_idx, gt) in frame.gt.iter().enumerate() {
+        if ignored_gt[gt_idx] == Ignore::Other {
+            continue;
+        }
+
+        let mut det_idx = None;
+        let mut valid_detection = NO_DETECTION;
+        let mut max_iou = 0.0;
+        let mut assigned_ignored_det = false;
+
+        for (idx, det) in frame.det.iter().enumerate() {
+            if ignored_det[idx] == Ignore::Other || assigned[idx] || below_thresh[idx] {
+                continue;
+            }
+
+            let overlap = overlaps[idx][gt_idx];
+            if overlap <= min_overlap {
+                continue;
+            }
+
+            if !compute_fp {
+                // Find the detection with the highest score.
+                if det.score > valid_detection {
+                    det_idx = Some(idx);
+                    valid_detection = det.score;
+                }
+            } else if (overlap > max_iou || assigned_ignored_det) && ignored_det[idx] == Ignore::No
+            {
+                // Find the detection with the highest overlap,
+                // preferring detections that are not ignored.
+                max_iou = overlap;
+                det_idx = Some(idx);
+                valid_detection = 1.0;
+                assigned_ignored_det = false;
+            } else if valid_detection == NO_DETECTION && ignored_det[idx] == Ignore::Yes {
+                det_idx = Some(idx);
+                valid_detection = 1.0;
+                assigned_ignored_det = true;
+            }
+        }
+
+        match det_idx {
+            None => {
+                if ignored_gt[gt_idx] == Ignore::No {
+                    stats.fn_ += 1;
+                }
+            }
+            Some(det_idx) => {
+                if ignored_gt[gt_idx] == Ignore::No && ignored_det[det_idx] != Ignore::Yes {
+                    let det = &frame.det[det_idx];
+                    stats.tp += 1;
+                    stats.scores.push(det.score);
+                    if compute_aos {
+                        deltas.push(gt.alpha.as_radians() - det.label.alpha.as_radians());
+                    }
+                }
+                assigned[det_idx] = true;
+            }
+        }
+    }
+
+    if compute_fp {
+        let is_candidate = |assigned: &[bool], idx: usize| {
+            !assigned[idx] && ignored_det[idx] == Ignore::No && !below_thresh[idx]
+        };
+
+        stats.fp = (0..frame.det.len())
+            .filter(|&idx| is_candidate(&assigned, idx))
+            .count();
+
+        // Do not count detections in DontCare areas as false
+        // positives.
+        if metric == Metric::Bbox {
+            let min_overlap = metric.min_overlap(class);
+            for dont_care in &frame.dont_care {
+                for (idx, det) in frame.det.iter().enumerate() {
+                    if is_candidate(&assigned, idx)
+                        && bbox_ioa(&det.label.bbox, dont_care) > min_overlap
+                    {
+                        assigned[idx] = true;
+                        stats.fp -= 1;
+                    }
+                }
+            }
+        }
+
+        if compute_aos && (stats.tp > 0 || stats.fp > 0) {
+            // False positives have zero similarity.
+            let similarity = deltas.iter().map(|delta| (1.0 + delta.cos()) / 2.0).sum();
+            stats.similarity = Some(similarity);
+        }
+    }
+
+    stats
+}
+
+/// Selects the score thresholds that sample the recall evenly.
+fn get_thresholds(mut scores: Vec<f64>, num_gt: usize) -> Vec<f64> {
+    scores.sort_by(|lhs, rhs| rhs.total_cmp(lhs));
+
+    let num_gt = num_gt as f64;
+    let mut thresholds = vec![];
+    let mut current_recall = 0.0;
+
+    for (idx, &score) in scores.iter().enumerate() {
+        let l_recall = (idx + 1) as f64 / num_gt;
+        let is_last = idx + 1 == scores.len();
+        let r_recall = if is_last {
+            l_recall
+        } else {
+            (idx + 2) as f64 / num_gt
+        };
+
+        if !is_last && (r_recall - current_recall) < (current_recall - l_recall) {
+            continue;
+        }
+
+        thresholds.push(score);
+        current_recall += 1.0 / (NUM_SAMPLE_POINTS as f64 - 1.0);
+    }
+
+    thresholds
+}
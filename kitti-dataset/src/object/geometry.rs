@@ -0,0 +1,106 @@
+use super::{Extents, Label, Location};
+use measurements::Angle;
+
+/// Corner indices of the six box faces in the order of front, right,
+/// back, left, bottom and top. The front face points to the heading
+/// direction, and the right face is on the right of an observer
+/// facing the heading with the camera y-axis pointing down.
+pub const BOX_FACES: [[usize; 4]; 6] = [
+    [0, 1, 5, 4],
+    [1, 2, 6, 5],
+    [2, 3, 7, 6],
+    [3, 0, 4, 7],
+    [0, 1, 2, 3],
+    [4, 5, 6, 7],
+];
+
+/// Corner index pairs of the twelve box edges.
+pub const BOX_EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [1, 2],
+    [2, 3],
+    [3, 0],
+    [4, 5],
+    [5, 6],
+    [6, 7],
+    [7, 4],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+impl Label {
+    /// Computes the eight box corners in camera coordinates in
+    /// meters.
+    ///
+    /// The corner order follows the KITTI devkit. Corners 0 to 3 lie
+    /// on the bottom face and corners 4 to 7 on the top face, where
+    /// corner `i + 4` sits right above corner `i`. Use [BOX_FACES]
+    /// and [BOX_EDGES] to index faces and edges.
+    pub fn corners(&self) -> [[f64; 3]; 8] {
+        box_corners(&self.extents, &self.location, self.rotation_y)
+    }
+
+    /// Computes the box center in camera coordinates in meters.
+    ///
+    /// Note that [Label::location] is the bottom center, while the
+    /// center is half the height above it.
+    pub fn center(&self) -> [f64; 3] {
+        box_center(&self.extents, &self.location)
+    }
+
+    /// Gets the corners of each face in the order of [BOX_FACES].
+    pub fn faces(&self) -> [[[f64; 3]; 4]; 6] {
+        box_faces(&self.corners())
+    }
+
+    /// Gets the end points of each edge in the order of [BOX_EDGES].
+    pub fn edges(&self) -> [[[f64; 3]; 2]; 12] {
+        box_edges(&self.corners())
+    }
+}
+
+pub(crate) fn box_corners(
+    extents: &Extents,
+    location: &Location,
+    rotation_y: Angle,
+) -> [[f64; 3]; 8] {
+    let h = extents.height.as_meters();
+    let w = extents.width.as_meters();
+    let l = extents.length.as_meters();
+    let [tx, ty, tz] = location_to_array(location);
+    let (sin, cos) = rotation_y.as_radians().sin_cos();
+
+    let xs = [l / 2.0, l / 2.0, -l / 2.0, -l / 2.0];
+    let zs = [w / 2.0, -w / 2.0, -w / 2.0, w / 2.0];
+
+    let mut corners = [[0.0; 3]; 8];
+    for idx in 0..8 {
+        let x = xs[idx % 4];
+        let y = if idx < 4 { 0.0 } else { -h };
+        let z = zs[idx % 4];
+
+        // Rotate around the y-axis and translate to the location.
+        corners[idx] = [cos * x + sin * z + tx, y + ty, -sin * x + cos * z + tz];
+    }
+    corners
+}
+
+pub(crate) fn box_center(extents: &Extents, location: &Location) -> [f64; 3] {
+    let [x, y, z] = location_to_array(location);
+    [x, y - extents.height.as_meters() / 2.0, z]
+}
+
+pub(crate) fn box_faces(corners: &[[f64; 3]; 8]) -> [[[f64; 3]; 4]; 6] {
+    BOX_FACES.map(|face| face.map(|idx| corners[idx]))
+}
+
+pub(crate) fn box_edges(corners: &[[f64; 3]; 8]) -> [[[f64; 3]; 2]; 12] {
+    BOX_EDGES.map(|edge| edge.map(|idx| corners[idx]))
+}
+
+fn location_to_array(location: &Location) -> [f64; 3] {
+    let Location { x, y, z } = location;
+    [x.as_meters(), y.as_meters(), z.as_meters()]
+}
@@ -0,0 +1,231 @@
+//! Overlap measures between image boxes and 3D boxes.
+//!
+//! ```ignore
+//! let iou = gt.box3d_iou(&det);
+//! let matrix = bev_iou_matrix(&gts, &dets);
+//! ```
+
+use super::{geometry::box_corners, BoundingBox, Label};
+use std::borrow::Borrow;
+
+impl BoundingBox {
+    /// Computes the intersection over union with another box.
+    pub fn iou(&self, other: &BoundingBox) -> f64 {
+        bbox_iou(self, other)
+    }
+
+    /// Computes the intersection area over the area of this box.
+    pub fn ioa(&self, other: &BoundingBox) -> f64 {
+        bbox_ioa(self, other)
+    }
+
+    pub fn area(&self) -> f64 {
+        bbox_area(self)
+    }
+}
+
+impl Label {
+    /// Computes the intersection over union of the 2D boxes in the
+    /// image.
+    pub fn bbox_iou(&self, other: &Label) -> f64 {
+        bbox_iou(&self.bbox, &other.bbox)
+    }
+
+    /// Computes the intersection over union of the rotated box
+    /// footprints in bird's eye view.
+    pub fn bev_iou(&self, other: &Label) -> f64 {
+        bev_iou(self, other)
+    }
+
+    /// Computes the intersection over union of the rotated 3D boxes.
+    pub fn box3d_iou(&self, other: &Label) -> f64 {
+        box3d_iou(self, other)
+    }
+}
+
+/// Computes the IoU matrix of image boxes, where `matrix[i][j]` is the
+/// IoU of `lhs[i]` and `rhs[j]`.
+pub fn bbox_iou_matrix<A, B>(lhs: &[A], rhs: &[B]) -> Vec<Vec<f64>>
+where
+    A: Borrow<BoundingBox>,
+    B: Borrow<BoundingBox>,
+{
+    iou_matrix(lhs, rhs, bbox_iou)
+}
+
+/// Computes the bird's eye view IoU matrix of 3D boxes, where
+/// `matrix[i][j]` is the IoU of `lhs[i]` and `rhs[j]`.
+pub fn bev_iou_matrix<A, B>(lhs: &[A], rhs: &[B]) -> Vec<Vec<f64>>
+where
+    A: Borrow<Label>,
+    B: Borrow<Label>,
+{
+    iou_matrix(lhs, rhs, bev_iou)
+}
+
+/// Computes the 3D IoU matrix of 3D boxes, where `matrix[i][j]` is
+/// the IoU of `lhs[i]` and `rhs[j]`.
+pub fn box3d_iou_matrix<A, B>(lhs: &[A], rhs: &[B]) -> Vec<Vec<f64>>
+where
+    A: Borrow<Label>,
+    B: Borrow<Label>,
+{
+    iou_matrix(lhs, rhs, box3d_iou)
+}
+
+fn iou_matrix<T, A, B, F>(lhs: &[A], rhs: &[B], iou: F) -> Vec<Vec<f64>>
+where
+    T: ?Sized,
+    A: Borrow<T>,
+    B: Borrow<T>,
+    F: Fn(&T, &T) -> f64,
+{
+    lhs.iter()
+        .map(|lhs| {
+            rhs.iter()
+                .map(|rhs| iou(lhs.borrow(), rhs.borrow()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Computes the intersection over union of two image boxes.
+pub fn bbox_iou(lhs: &BoundingBox, rhs: &BoundingBox) -> f64 {
+    let inter = bbox_intersection(lhs, rhs);
+    if inter <= 0.0 {
+        return 0.0;
+    }
+    inter / (bbox_area(lhs) + bbox_area(rhs) - inter)
+}
+
+/// Computes the intersection area over the area of the first box.
+pub fn bbox_ioa(lhs: &BoundingBox, rhs: &BoundingBox) -> f64 {
+    let inter = bbox_intersection(lhs, rhs);
+    if inter <= 0.0 {
+        return 0.0;
+    }
+    inter / bbox_area(lhs)
+}
+
+/// Computes the intersection over union of the ground footprints of
+/// two 3D boxes on the x-z plane.
+pub fn bev_iou(lhs: &Label, rhs: &Label) -> f64 {
+    let lhs_poly = footprint(lhs);
+    let rhs_poly = footprint(rhs);
+    let inter = polygon_area(&clip_polygon(&lhs_poly, &rhs_poly));
+    if inter <= 0.0 {
+        return 0.0;
+    }
+    inter / (polygon_area(&lhs_poly) + polygon_area(&rhs_poly) - inter)
+}
+
+/// Computes the intersection over union of two 3D boxes.
+pub fn box3d_iou(lhs: &Label, rhs: &Label) -> f64 {
+    // The y-axis points downwards and the location is the bottom
+    // center, so the box spans from y - h to y.
+    let y_range = |label: &Label| {
+        let y = label.location.y.as_meters();
+        (y - label.extents.height.as_meters(), y)
+    };
+    let (lhs_ymin, lhs_ymax) = y_range(lhs);
+    let (rhs_ymin, rhs_ymax) = y_range(rhs);
+    let inter_height = lhs_ymax.min(rhs_ymax) - lhs_ymin.max(rhs_ymin);
+    if inter_height <= 0.0 {
+        return 0.0;
+    }
+
+    let lhs_poly = footprint(lhs);
+    let rhs_poly = footprint(rhs);
+    let inter = polygon_area(&clip_polygon(&lhs_poly, &rhs_poly)) * inter_height;
+    if inter <= 0.0 {
+        return 0.0;
+    }
+
+    let lhs_vol = polygon_area(&lhs_poly) * (lhs_ymax - lhs_ymin);
+    let rhs_vol = polygon_area(&rhs_poly) * (rhs_ymax - rhs_ymin);
+    inter / (lhs_vol + rhs_vol - inter)
+}
+
+fn bbox_intersection(lhs: &BoundingBox, rhs: &BoundingBox) -> f64 {
+    let iw = lhs.xmax.min(rhs.xmax) - lhs.xmin.max(rhs.xmin);
+    let ih = lhs.ymax.min(rhs.ymax) - lhs.ymin.max(rhs.ymin);
+    if iw <= 0.0 || ih <= 0.0 {
+        return 0.0;
+    }
+    iw * ih
+}
+
+fn bbox_area(bbox: &BoundingBox) -> f64 {
+    (bbox.xmax - bbox.xmin) * (bbox.ymax - bbox.ymin)
+}
+
+/// Gets the bottom face of the box on the x-z plane in
+/// counter-clockwise order.
+fn footprint(label: &Label) -> Vec<[f64; 2]> {
+    let corners = box_corners(&label.extents, &label.location, label.rotation_y);
+    let mut poly: Vec<[f64; 2]> = corners[..4].iter().map(|&[x, _, z]| [x, z]).collect();
+    if signed_area(&poly) < 0.0 {
+        poly.reverse();
+    }
+    poly
+}
+
+fn signed_area(poly: &[[f64; 2]]) -> f64 {
+    let n = poly.len();
+    (0..n)
+        .map(|idx| {
+            let [x1, y1] = poly[idx];
+            let [x2, y2] = poly[(idx + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn polygon_area(poly: &[[f64; 2]]) -> f64 {
+    signed_area(poly).abs()
+}
+
+/// Clips a polygon by a convex counter-clockwise polygon using the
+/// Sutherland–Hodgman algorithm.
+fn clip_polygon(subject: &[[f64; 2]], clip: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let cross = |[ax, ay]: [f64; 2], [bx, by]: [f64; 2], [px, py]: [f64; 2]| {
+        (bx - ax) * (py - ay) - (by - ay) * (px - ax)
+    };
+
+    let mut output = subject.to_vec();
+
+    for idx in 0..clip.len() {
+        if output.is_empty() {
+            break;
+        }
+
+        let a = clip[idx];
+        let b = clip[(idx + 1) % clip.len()];
+        let input = std::mem::take(&mut output);
+
+        for (jdx, &curr) in input.iter().enumerate() {
+            let prev = input[(jdx + input.len() - 1) % input.len()];
+            let curr_side = cross(a, b, curr);
+            let prev_side = cross(a, b, prev);
+
+            if curr_side >= 0.0 {
+                if prev_side < 0.0 {
+                    output.push(intersect(prev, curr, prev_side, curr_side));
+                }
+                output.push(curr);
+            } else if prev_side >= 0.0 {
+                output.push(intersect(prev, curr, prev_side, curr_side));
+            }
+        }
+    }
+
+    output
+}
+
+/// Finds the point on segment `p`-`q` where the side value crosses
+/// zero.
+fn intersect(p: [f64; 2], q: [f64; 2], p_side: f64, q_side: f64) -> [f64; 2] {
+    let ratio = p_side / (p_side - q_side);
+    [p[0] + (q[0] - p[0]) * ratio, p[1] + (q[1] - p[1]) * ratio]
+}
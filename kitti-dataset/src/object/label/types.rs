@@ -6,11 +6,15 @@ use measurements::{Angle, Length};
 use noisy_float::prelude::*;
 use num_derive::FromPrimitive;
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(from = "SerializedLabel", into = "SerializedLabel")]
 pub struct Label {
-    pub class: String,
+    pub class: Class,
     pub truncation: Option<Truncation>,
     pub occlusion: Option<Occlusion>,
     pub alpha: Angle,
@@ -22,7 +26,7 @@ pub struct Label {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SerializedLabel {
-    pub class: String,
+    pub class: Class,
     #[serde(with = "object_truncation")]
     pub truncation: Option<Truncation>,
     #[serde(with = "occlusion")]
@@ -131,6 +135,112 @@ impl From<Label> for SerializedLabel {
     }
 }
 
+/// The object class of a label.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Class {
+    Car,
+    Van,
+    Truck,
+    Pedestrian,
+    PersonSitting,
+    Cyclist,
+    Tram,
+    Misc,
+    /// A person in the tracking labels, which is evaluated as a
+    /// neighbouring class of pedestrians.
+    Person,
+    DontCare,
+    /// A class name not defined by KITTI.
+    Other(String),
+}
+
+impl Class {
+    /// Gets the class name spelled as in KITTI label files.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Class::Car => "Car",
+            Class::Van => "Van",
+            Class::Truck => "Truck",
+            Class::Pedestrian => "Pedestrian",
+            Class::PersonSitting => "Person_sitting",
+            Class::Cyclist => "Cyclist",
+            Class::Tram => "Tram",
+            Class::Misc => "Misc",
+            Class::Person => "Person",
+            Class::DontCare => "DontCare",
+            Class::Other(name) => name,
+        }
+    }
+
+    /// Parses a class name. Names not defined by KITTI are stored in
+    /// [Class::Other]. Use [FromStr] to reject them instead.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Car" => Class::Car,
+            "Van" => Class::Van,
+            "Truck" => Class::Truck,
+            "Pedestrian" => Class::Pedestrian,
+            "Person_sitting" => Class::PersonSitting,
+            "Cyclist" => Class::Cyclist,
+            "Tram" => Class::Tram,
+            "Misc" => Class::Misc,
+            "Person" => Class::Person,
+            "DontCare" => Class::DontCare,
+            _ => Class::Other(name.to_string()),
+        }
+    }
+
+    /// Returns true if the class is defined by KITTI.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Class::Other(_))
+    }
+}
+
+impl FromStr for Class {
+    type Err = Error;
+
+    /// Parses a class name and fails with
+    /// [Error::InvalidClassName] if it is not defined by KITTI.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let class = Class::from_name(name);
+        if !class.is_known() {
+            return Err(Error::InvalidClassName(name.to_string()));
+        }
+        Ok(class)
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Class {
+    fn from(name: &str) -> Self {
+        Class::from_name(name)
+    }
+}
+
+impl From<String> for Class {
+    fn from(name: String) -> Self {
+        match Class::from_name(&name) {
+            Class::Other(_) => Class::Other(name),
+            class => class,
+        }
+    }
+}
+
+impl From<Class> for String {
+    fn from(class: Class) -> Self {
+        match class {
+            Class::Other(name) => name,
+            class => class.as_str().to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive)]
 #[repr(u8)]
 pub enum Occlusion {
@@ -0,0 +1,237 @@
+use crate::{common::indexed_files, Error};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{self, prelude::*, BufReader, BufWriter, Cursor},
+    path::Path,
+    str::FromStr,
+};
+
+/// A set of frame IDs in the `ImageSets/{train,val,test}.txt` format,
+/// which lists one zero-padded frame ID per line.
+///
+/// ```text
+/// 000000
+/// 000003
+/// 000007
+/// ```
+///
+/// The IDs are kept sorted and deduplicated. Random splits are
+/// generated by an internal seeded generator, so the same seed gives
+/// the same split on every platform and release.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Split {
+    ids: Vec<usize>,
+}
+
+impl Split {
+    pub fn new<I>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut ids: Vec<usize> = ids.into_iter().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        Self { ids }
+    }
+
+    /// Gets the frame IDs in ascending order.
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.ids.binary_search(&id).is_ok()
+    }
+
+    /// Randomly splits the IDs into two parts, where the first part
+    /// takes `ratio` of the IDs rounded to the nearest integer.
+    ///
+    /// ```ignore
+    /// let (train, val) = Split::random(0..7481, 0.5, 42);
+    /// ```
+    pub fn random<I>(ids: I, ratio: f64, seed: u64) -> (Split, Split)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut ids = Self::new(ids).ids;
+        let mut rng = SplitMix64(seed);
+        rng.shuffle(&mut ids);
+
+        let num_first = split_count(ids.len(), ratio);
+        let second = ids.split_off(num_first);
+        (Self::new(ids), Self::new(second))
+    }
+
+    /// Randomly splits the IDs into two parts while keeping the
+    /// proportion of each stratum, such as the dominant class of a
+    /// frame. Each stratum is split by `ratio` independently. An ID
+    /// listed more than once belongs to the stratum of its first
+    /// occurrence, so that it lands in exactly one part.
+    ///
+    /// ```ignore
+    /// let strata = frames.iter().map(|frame| (frame.id, frame.dominant_class()));
+    /// let (train, val) = Split::stratified(strata, 0.5, 42);
+    /// ```
+    pub fn stratified<I, K>(items: I, ratio: f64, seed: u64) -> (Split, Split)
+    where
+        I: IntoIterator<Item = (usize, K)>,
+        K: Ord,
+    {
+        let mut seen = BTreeSet::new();
+        let mut strata: BTreeMap<K, Vec<usize>> = BTreeMap::new();
+        for (id, key) in items {
+            if seen.insert(id) {
+                strata.entry(key).or_default().push(id);
+            }
+        }
+
+        let mut rng = SplitMix64(seed);
+        let mut first = vec![];
+        let mut second = vec![];
+
+        for mut ids in strata.into_values() {
+            ids.sort_unstable();
+            rng.shuffle(&mut ids);
+
+            let num_first = split_count(ids.len(), ratio);
+            second.extend(ids.split_off(num_first));
+            first.extend(ids);
+        }
+
+        (Self::new(first), Self::new(second))
+    }
+
+    pub fn from_reader<R>(reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let reader = BufReader::new(reader);
+
+        let ids: Vec<usize> = reader
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| -> Option<Result<_, Error>> {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(err) => return Some(Err(err.into())),
+                };
+                let token = line.trim();
+                if token.is_empty() {
+                    return None;
+                }
+
+                let id = token
+                    .bytes()
+                    .all(|b| b.is_ascii_digit())
+                    .then(|| token.parse().ok())
+                    .flatten()
+                    .ok_or_else(|| Error::InvalidSplitId {
+                        line: idx + 1,
+                        token: token.to_string(),
+                    });
+                Some(id)
+            })
+            .try_collect()?;
+
+        Ok(Self::new(ids))
+    }
+
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Loads all split files in an `ImageSets` directory, keyed by the
+    /// file stem such as `train`, `val` and `test`.
+    pub fn map_from_dir<P>(dir: P) -> Result<BTreeMap<String, Split>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        indexed_files::list_files_by_stem(dir.as_ref(), "txt")?
+            .into_iter()
+            .map(|(name, path)| Ok((name, Self::from_path(path)?)))
+            .collect()
+    }
+
+    pub fn write_to_writer<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for id in &self.ids {
+            writeln!(writer, "{id:06}")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_to_path<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let writer = BufWriter::new(File::create(path)?);
+        self.write_to_writer(writer)
+    }
+
+    pub fn write_to_string(&self) -> io::Result<String> {
+        let mut buf = vec![];
+        self.write_to_writer(&mut buf)?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+}
+
+impl FromStr for Split {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_reader(Cursor::new(text))
+    }
+}
+
+impl FromIterator<usize> for Split {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+fn split_count(len: usize, ratio: f64) -> usize {
+    ((len as f64 * ratio.clamp(0.0, 1.0)).round() as usize).min(len)
+}
+
+/// The SplitMix64 generator, which is small and has a fixed output
+/// sequence for a seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Draws a number in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// Shuffles the slice with the Fisher–Yates algorithm.
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for idx in (1..slice.len()).rev() {
+            let jdx = self.below(idx + 1);
+            slice.swap(idx, jdx);
+        }
+    }
+}
@@ -1,4 +1,5 @@
-mod pose;
+pub mod eval;
+pub(crate) mod pose;
 
 pub use crate::calib_codegen::OdometryCalibration as Calibration;
 pub use pose::Pose;
@@ -0,0 +1,296 @@
+//! Odometry evaluation compatible with the official KITTI
+//! `evaluate_odometry` devkit.
+//!
+//! The drift is measured over path segments of 100 to 800 meters
+//! starting every 10 frames. The translational error is reported in
+//! percent and the rotational error in degrees per meter. The
+//! absolute trajectory error (ATE) and the relative pose error (RPE)
+//! between consecutive frames are reported as well.
+//!
+//! ```ignore
+//! let result = evaluate_sequence(&gt_poses, &est_poses)?;
+//! let drift = result.drift().unwrap();
+//! println!("{}% {}deg/m", drift.translation, drift.rotation);
+//! ```
+
+use super::{
+    pose::{affine_compose, affine_inverse, Affine},
+    Pose,
+};
+use crate::Error;
+
+/// The segment lengths in meters.
+pub const SEGMENT_LENGTHS: [f64; 8] = [100.0, 200.0, 300.0, 400.0, 500.0, 600.0, 700.0, 800.0];
+
+/// The speeds in meters per second used for the per-speed breakdown.
+/// Each bin covers segments within 2 m/s of the speed.
+pub const SPEEDS: [f64; 12] = [
+    2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0, 20.0, 22.0, 24.0,
+];
+
+/// The frame step between the starting frames of segments.
+const STEP_SIZE: usize = 10;
+
+/// The frame interval in seconds.
+const FRAME_INTERVAL: f64 = 0.1;
+
+/// The minimum number of segments to report a breakdown bin, matching
+/// the devkit.
+const MIN_BIN_SEGMENTS: usize = 3;
+
+/// The drift of one path segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentError {
+    pub first_frame: usize,
+    /// The segment length in meters.
+    pub length: f64,
+    /// The average speed in meters per second.
+    pub speed: f64,
+    /// The translational error divided by the length.
+    pub translation_error: f64,
+    /// The rotational error in radians divided by the length.
+    pub rotation_error: f64,
+}
+
+/// The average drift over a set of segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    /// The translational error in percent.
+    pub translation: f64,
+    /// The rotational error in degrees per meter.
+    pub rotation: f64,
+    pub num_segments: usize,
+}
+
+impl Drift {
+    fn from_segments<'a, I>(segments: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a SegmentError>,
+    {
+        let mut num_segments = 0;
+        let mut translation = 0.0;
+        let mut rotation = 0.0;
+        for segment in segments {
+            num_segments += 1;
+            translation += segment.translation_error;
+            rotation += segment.rotation_error;
+        }
+
+        (num_segments > 0).then(|| Self {
+            translation: translation / num_segments as f64 * 100.0,
+            rotation: (rotation / num_segments as f64).to_degrees(),
+            num_segments,
+        })
+    }
+}
+
+/// The evaluation result of one sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceResult {
+    pub segments: Vec<SegmentError>,
+    /// The root mean square of the position errors in meters.
+    pub ate: f64,
+    /// The root mean square of the frame-to-frame translational errors
+    /// in meters.
+    pub rpe_translation: f64,
+    /// The root mean square of the frame-to-frame rotational errors in
+    /// degrees.
+    pub rpe_rotation: f64,
+}
+
+impl SequenceResult {
+    /// Gets the average drift over all segments. It returns `None` if
+    /// the trajectory is shorter than 100 meters.
+    pub fn drift(&self) -> Option<Drift> {
+        Drift::from_segments(&self.segments)
+    }
+
+    /// Gets the average drift for each of [SEGMENT_LENGTHS].
+    pub fn drift_per_length(&self) -> Vec<(f64, Option<Drift>)> {
+        drift_per_length(&self.segments)
+    }
+
+    /// Gets the average drift for each of [SPEEDS].
+    pub fn drift_per_speed(&self) -> Vec<(f64, Option<Drift>)> {
+        drift_per_speed(&self.segments)
+    }
+}
+
+/// The evaluation result of multiple sequences.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalResult {
+    pub sequences: Vec<SequenceResult>,
+}
+
+impl EvalResult {
+    /// Gets the average drift over the segments of all sequences, the
+    /// number reported by the benchmark.
+    pub fn drift(&self) -> Option<Drift> {
+        Drift::from_segments(self.segments())
+    }
+
+    pub fn drift_per_length(&self) -> Vec<(f64, Option<Drift>)> {
+        let segments: Vec<_> = self.segments().cloned().collect();
+        drift_per_length(&segments)
+    }
+
+    pub fn drift_per_speed(&self) -> Vec<(f64, Option<Drift>)> {
+        let segments: Vec<_> = self.segments().cloned().collect();
+        drift_per_speed(&segments)
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &SegmentError> {
+        self.sequences.iter().flat_map(|seq| &seq.segments)
+    }
+}
+
+/// Evaluates estimated poses against ground truth poses for multiple
+/// sequences.
+pub fn evaluate<I, G, E>(sequences: I) -> Result<EvalResult, Error>
+where
+    I: IntoIterator<Item = (G, E)>,
+    G: AsRef<[Pose]>,
+    E: AsRef<[Pose]>,
+{
+    let sequences: Vec<_> = sequences
+        .into_iter()
+        .map(|(gt, est)| evaluate_sequence(gt.as_ref(), est.as_ref()))
+        .collect::<Result<_, _>>()?;
+    Ok(EvalResult { sequences })
+}
+
+/// Evaluates estimated poses against ground truth poses of one
+/// sequence. Both must have the same number of poses.
+pub fn evaluate_sequence(gt: &[Pose], est: &[Pose]) -> Result<SequenceResult, Error> {
+    if gt.len() != est.len() {
+        return Err(Error::PoseCountMismatch {
+            expect: gt.len(),
+            found: est.len(),
+        });
+    }
+
+    let gt: Vec<Affine> = gt.iter().map(Pose::to_affine).collect();
+    let est: Vec<Affine> = est.iter().map(Pose::to_affine).collect();
+    let dist = trajectory_distances(&gt);
+
+    let mut segments = vec![];
+    for first_frame in (0..gt.len()).step_by(STEP_SIZE) {
+        for length in SEGMENT_LENGTHS {
+            let Some(last_frame) = last_frame_from_segment_length(&dist, first_frame, length)
+            else {
+                continue;
+            };
+
+            let error = relative_error(&gt, &est, first_frame, last_frame);
+            let num_frames = last_frame - first_frame + 1;
+
+            segments.push(SegmentError {
+                first_frame,
+                length,
+                speed: length / (FRAME_INTERVAL * num_frames as f64),
+                translation_error: translation_error(&error) / length,
+                rotation_error: rotation_error(&error) / length,
+            });
+        }
+    }
+
+    let ate = rms(gt.iter().zip(&est).map(|(gt, est)| {
+        let diff: [f64; 3] = std::array::from_fn(|idx| gt[idx][3] - est[idx][3]);
+        norm(&diff)
+    }));
+    let rpe: Vec<Affine> = (1..gt.len())
+        .map(|idx| relative_error(&gt, &est, idx - 1, idx))
+        .collect();
+    let rpe_translation = rms(rpe.iter().map(translation_error));
+    let rpe_rotation = rms(rpe.iter().map(|error| rotation_error(error).to_degrees()));
+
+    Ok(SequenceResult {
+        segments,
+        ate,
+        rpe_translation,
+        rpe_rotation,
+    })
+}
+
+fn drift_per_length(segments: &[SegmentError]) -> Vec<(f64, Option<Drift>)> {
+    SEGMENT_LENGTHS
+        .iter()
+        .map(|&length| {
+            let drift = Drift::from_segments(segments.iter().filter(|seg| seg.length == length))
+                .filter(|drift| drift.num_segments >= MIN_BIN_SEGMENTS);
+            (length, drift)
+        })
+        .collect()
+}
+
+fn drift_per_speed(segments: &[SegmentError]) -> Vec<(f64, Option<Drift>)> {
+    SPEEDS
+        .iter()
+        .map(|&speed| {
+            let drift = Drift::from_segments(
+                segments
+                    .iter()
+                    .filter(|seg| (seg.speed - speed).abs() < 2.0),
+            )
+            .filter(|drift| drift.num_segments >= MIN_BIN_SEGMENTS);
+            (speed, drift)
+        })
+        .collect()
+}
+
+/// Computes the error transform between the estimated and ground
+/// truth motions from the first to the last frame.
+fn relative_error(gt: &[Affine], est: &[Affine], first: usize, last: usize) -> Affine {
+    let gt_delta = affine_compose(&affine_inverse(&gt[first]), &gt[last]);
+    let est_delta = affine_compose(&affine_inverse(&est[first]), &est[last]);
+    affine_compose(&affine_inverse(&est_delta), &gt_delta)
+}
+
+/// Computes the accumulated path length at each frame.
+fn trajectory_distances(poses: &[Affine]) -> Vec<f64> {
+    let mut dist = Vec::with_capacity(poses.len());
+    let mut sum = 0.0;
+    for (idx, pose) in poses.iter().enumerate() {
+        if idx > 0 {
+            let prev = &poses[idx - 1];
+            let diff: [f64; 3] = std::array::from_fn(|row| pose[row][3] - prev[row][3]);
+            sum += norm(&diff);
+        }
+        dist.push(sum);
+    }
+    dist
+}
+
+fn last_frame_from_segment_length(dist: &[f64], first_frame: usize, length: f64) -> Option<usize> {
+    (first_frame..dist.len()).find(|&idx| dist[idx] > dist[first_frame] + length)
+}
+
+fn rotation_error(error: &Affine) -> f64 {
+    let trace = error[0][0] + error[1][1] + error[2][2];
+    let d = 0.5 * (trace - 1.0);
+    d.clamp(-1.0, 1.0).acos()
+}
+
+fn translation_error(error: &Affine) -> f64 {
+    norm(&[error[0][3], error[1][3], error[2][3]])
+}
+
+fn norm(vec: &[f64; 3]) -> f64 {
+    vec.iter().map(|value| value * value).sum::<f64>().sqrt()
+}
+
+fn rms<I>(values: I) -> f64
+where
+    I: IntoIterator<Item = f64>,
+{
+    let mut count = 0;
+    let mut sum = 0.0;
+    for value in values {
+        count += 1;
+        sum += value * value;
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    (sum / count as f64).sqrt()
+}
@@ -7,31 +7,71 @@ use std::{
     path::Path,
 };
 
+/// A 3x4 rigid transform in double precision used for pose
+/// arithmetic.
+pub(crate) type Affine = [[f64; 4]; 3];
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Pose(pub ProjectionMatrix);
 
 impl Pose {
+    pub fn identity() -> Self {
+        Self(ProjectionMatrix([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]))
+    }
+
+    pub fn translation(&self) -> [f32; 3] {
+        self.0 .0.map(|row| row[3])
+    }
+
+    /// Computes the inverse rigid transform, assuming the rotation
+    /// part is orthonormal.
+    pub fn inverse(&self) -> Pose {
+        Self::from_affine(&affine_inverse(&self.to_affine()))
+    }
+
+    /// Computes the transform that applies `other` first and then
+    /// `self`, that is the matrix product `self * other`.
+    pub fn compose(&self, other: &Pose) -> Pose {
+        Self::from_affine(&affine_compose(&self.to_affine(), &other.to_affine()))
+    }
+
+    pub(crate) fn to_affine(&self) -> Affine {
+        self.0 .0.map(|row| row.map(f64::from))
+    }
+
+    pub(crate) fn from_affine(affine: &Affine) -> Self {
+        Self(ProjectionMatrix(
+            affine.map(|row| row.map(|value| value as f32)),
+        ))
+    }
+
+    /// Reads poses line by line. Blank lines and comment lines
+    /// starting with `#` are skipped.
     pub fn iter_from_reader<R>(reader: R) -> impl Iterator<Item = Result<Pose, Error>>
     where
         R: Read,
     {
         let reader = BufReader::new(reader);
 
-        reader.lines().map(|line| -> Result<_, Error> {
-            let line = line?;
-            let line = line.trim();
-            let tokens = line.split_ascii_whitespace();
-            let values: Vec<f32> = tokens.map(|tk| tk.parse()).try_collect().unwrap();
-            let values: [f32; 12] = values.try_into().unwrap();
-            let [r11, r12, r13, tx, r21, r22, r23, ty, r31, r32, r33, tz] = values;
-            let mat = [
-                [r11, r12, r13, tx],
-                [r21, r22, r23, ty],
-                [r31, r32, r33, tz],
-            ];
-
-            Ok(Pose(ProjectionMatrix(mat)))
-        })
+        reader
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| -> Option<Result<_, Error>> {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(err) => return Some(Err(err.into())),
+                };
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+
+                Some(parse_pose_line(line, idx + 1))
+            })
     }
 
     pub fn iter_from_path<P>(path: P) -> Result<impl Iterator<Item = Result<Pose, Error>>, Error>
@@ -62,7 +102,7 @@ impl Pose {
         Ok(poses)
     }
 
-    pub fn vec_from_str<P>(text: &str) -> Result<Vec<Pose>, Error> {
+    pub fn vec_from_str(text: &str) -> Result<Vec<Pose>, Error> {
         let poses: Vec<_> = Self::iter_from_str(text).try_collect()?;
         Ok(poses)
     }
@@ -108,3 +148,51 @@ impl Pose {
         Ok(String::from_utf8(buf).unwrap())
     }
 }
+
+fn parse_pose_line(line: &str, line_num: usize) -> Result<Pose, Error> {
+    let values: Vec<f32> = line
+        .split_ascii_whitespace()
+        .map(|token| {
+            token.parse().map_err(|_| Error::InvalidPoseToken {
+                line: line_num,
+                token: token.to_string(),
+            })
+        })
+        .try_collect()?;
+    let found = values.len();
+    let values: [f32; 12] = values.try_into().map_err(|_| Error::InvalidPoseLength {
+        line: line_num,
+        expect: 12,
+        found,
+    })?;
+    let [r11, r12, r13, tx, r21, r22, r23, ty, r31, r32, r33, tz] = values;
+    let mat = [
+        [r11, r12, r13, tx],
+        [r21, r22, r23, ty],
+        [r31, r32, r33, tz],
+    ];
+
+    Ok(Pose(ProjectionMatrix(mat)))
+}
+
+pub(crate) fn affine_inverse(affine: &Affine) -> Affine {
+    let mut inverse = [[0.0; 4]; 3];
+    for row in 0..3 {
+        for col in 0..3 {
+            inverse[row][col] = affine[col][row];
+        }
+        inverse[row][3] = -(0..3).map(|k| affine[k][row] * affine[k][3]).sum::<f64>();
+    }
+    inverse
+}
+
+pub(crate) fn affine_compose(lhs: &Affine, rhs: &Affine) -> Affine {
+    let mut product = [[0.0; 4]; 3];
+    for row in 0..3 {
+        for col in 0..4 {
+            product[row][col] = (0..3).map(|k| lhs[row][k] * rhs[k][col]).sum::<f64>();
+        }
+        product[row][3] += lhs[row][3];
+    }
+    product
+}
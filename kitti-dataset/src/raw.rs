@@ -0,0 +1,32 @@
+use crate::Error;
+use std::path::Path;
+
+pub use crate::{
+    calib_codegen::{CamToCamCalibration, ImuToVeloCalibration, VeloToCamCalibration},
+    tracking::Oxts,
+};
+
+/// The set of calibration files shipped with each recording date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    pub cam_to_cam: CamToCamCalibration,
+    pub velo_to_cam: VeloToCamCalibration,
+    pub imu_to_velo: ImuToVeloCalibration,
+}
+
+impl Calibration {
+    /// Loads `calib_cam_to_cam.txt`, `calib_velo_to_cam.txt` and
+    /// `calib_imu_to_velo.txt` from a date directory.
+    pub fn from_dir<P>(dir: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+
+        Ok(Self {
+            cam_to_cam: CamToCamCalibration::from_path(dir.join("calib_cam_to_cam.txt"))?,
+            velo_to_cam: VeloToCamCalibration::from_path(dir.join("calib_velo_to_cam.txt"))?,
+            imu_to_velo: ImuToVeloCalibration::from_path(dir.join("calib_imu_to_velo.txt"))?,
+        })
+    }
+}
@@ -0,0 +1,5 @@
+mod disparity;
+mod flow;
+
+pub use disparity::{DisparityCodec, DisparityMap};
+pub use flow::{FlowCodec, FlowField};
@@ -0,0 +1,47 @@
+use crate::common::{scaled_png, MapCodec, MaskedMap};
+use image::{DynamicImage, ImageBuffer, Luma};
+
+/// The scale of disparity values stored in 16-bit PNG files.
+const DISPARITY_SCALE: f32 = 256.0;
+
+/// A dense disparity map with a validity mask.
+///
+/// KITTI stores disparity maps in 16-bit grayscale PNG files, where a
+/// pixel value `v` encodes the disparity `v / 256` and zero marks an
+/// invalid pixel without ground truth. Negative and non-finite
+/// disparities cannot be stored and are invalid.
+///
+/// ```ignore
+/// let disp = DisparityMap::from_path("training/disp_noc_0/000000_10.png")?;
+/// if let Some(d) = disp.get(100, 200) {
+///     println!("{d}");
+/// }
+/// ```
+pub type DisparityMap = MaskedMap<DisparityCodec>;
+
+/// The PNG encoding of [DisparityMap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisparityCodec;
+
+impl MapCodec for DisparityCodec {
+    type Value = f32;
+    type Pixel = Luma<u16>;
+
+    const FORMAT: &'static str = "16-bit grayscale disparity";
+
+    fn as_image(image: &DynamicImage) -> Option<&ImageBuffer<Luma<u16>, Vec<u16>>> {
+        image.as_luma16()
+    }
+
+    fn decode(pixel: &Luma<u16>) -> Option<f32> {
+        scaled_png::decode(pixel, DISPARITY_SCALE)
+    }
+
+    fn encode(value: Option<f32>) -> Luma<u16> {
+        scaled_png::encode(value, DISPARITY_SCALE)
+    }
+
+    fn is_valid(value: f32) -> bool {
+        scaled_png::is_valid(value)
+    }
+}
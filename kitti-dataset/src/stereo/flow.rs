@@ -0,0 +1,60 @@
+use crate::common::{MapCodec, MaskedMap};
+use image::{DynamicImage, ImageBuffer, Rgb};
+
+/// The scale of flow values stored in 16-bit PNG files.
+const FLOW_SCALE: f32 = 64.0;
+
+/// The offset of flow values stored in 16-bit PNG files.
+const FLOW_OFFSET: f32 = 32768.0;
+
+/// A dense optical flow field of `[u, v]` values with a validity
+/// mask.
+///
+/// KITTI stores flow fields in 16-bit RGB PNG files. The red and green
+/// channels encode the horizontal and vertical flow `u` and `v` as
+/// `(value - 2^15) / 64`, and the blue channel is nonzero for valid
+/// pixels. Non-finite flows are invalid.
+///
+/// ```ignore
+/// let flow = FlowField::from_path("training/flow_occ/000000_10.png")?;
+/// if let Some([u, v]) = flow.get(100, 200) {
+///     println!("{u} {v}");
+/// }
+/// ```
+pub type FlowField = MaskedMap<FlowCodec>;
+
+/// The PNG encoding of [FlowField].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowCodec;
+
+impl MapCodec for FlowCodec {
+    type Value = [f32; 2];
+    type Pixel = Rgb<u16>;
+
+    const FORMAT: &'static str = "16-bit RGB flow";
+
+    fn as_image(image: &DynamicImage) -> Option<&ImageBuffer<Rgb<u16>, Vec<u16>>> {
+        image.as_rgb16()
+    }
+
+    fn decode(&Rgb([u, v, valid]): &Rgb<u16>) -> Option<[f32; 2]> {
+        let decode = |value: u16| (value as f32 - FLOW_OFFSET) / FLOW_SCALE;
+        (valid != 0).then(|| [decode(u), decode(v)])
+    }
+
+    /// Encodes the flow as the devkit does. Valid flows are scaled by
+    /// 64, offset by 2^15, truncated and clamped to the 16-bit range.
+    /// Invalid pixels are all zero.
+    fn encode(value: Option<[f32; 2]>) -> Rgb<u16> {
+        let encode =
+            |value: f32| (value * FLOW_SCALE + FLOW_OFFSET).clamp(0.0, u16::MAX as f32) as u16;
+        match value {
+            Some([u, v]) => Rgb([encode(u), encode(v), 1]),
+            None => Rgb([0; 3]),
+        }
+    }
+
+    fn is_valid([u, v]: [f32; 2]) -> bool {
+        u.is_finite() && v.is_finite()
+    }
+}
@@ -1,6 +1,9 @@
+pub mod eval;
 mod label;
 mod oxts;
+mod track_set;
 
 pub use crate::calib_codegen::TrackingCalibration as Calibration;
 pub use label::*;
-pub use oxts::Oxts;
+pub use oxts::{Oxts, OxtsFloatFormat, PoseFrame};
+pub use track_set::{Track, TrackSet};
@@ -0,0 +1,730 @@
+//! Multi-object tracking evaluation compatible with the KITTI
+//! tracking benchmark.
+//!
+//! The evaluation follows the KITTI preprocessing of TrackEval, the
+//! official evaluation code of the benchmark. For each class,
+//!
+//! - Ground truth boxes of the neighboring class, that is Van for Car
+//!   and Person for Pedestrian, and ground truth boxes that are
+//!   truncated or heavily occluded are not evaluated. Tracker boxes
+//!   matched to them are removed.
+//! - Unmatched tracker boxes not higher than 25 pixels or lying
+//!   within DontCare areas are removed.
+//!
+//! It reports the CLEAR MOT metrics and the HOTA metrics.
+//!
+//! ```ignore
+//! let sequences = vec![(ground_truth, tracker_output)];
+//! let result = evaluate(&sequences);
+//! let car = result.class(&Class::Car).unwrap();
+//! println!("MOTA {} HOTA {}", car.clear.mota(), car.hota.hota());
+//! ```
+
+use super::{Class, Label, Truncation};
+use crate::{assignment::linear_sum_assignment, object::iou::bbox_ioa};
+use std::collections::{BTreeMap, HashMap};
+
+/// The classes evaluated by the benchmark.
+pub const EVAL_CLASSES: [Class; 2] = [Class::Car, Class::Pedestrian];
+
+/// The number of localization thresholds of HOTA.
+pub const NUM_ALPHAS: usize = 19;
+
+const MIN_HEIGHT: f64 = 25.0;
+const MAX_OCCLUSION: i32 = 2;
+const MAX_TRUNCATION: f64 = 0.0;
+const MATCH_THRESHOLD: f64 = 0.5;
+const EPSILON: f64 = f64::EPSILON;
+
+/// Gets the localization thresholds 0.05, 0.1, ..., 0.95 of HOTA.
+pub fn alphas() -> [f64; NUM_ALPHAS] {
+    std::array::from_fn(|idx| (idx + 1) as f64 * 0.05)
+}
+
+/// The CLEAR MOT metrics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClearMetrics {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub id_switches: usize,
+    pub fragmentations: usize,
+    /// The number of ground truth tracks tracked for more than 80%
+    /// of their life span.
+    pub mostly_tracked: usize,
+    /// The number of ground truth tracks tracked for 20% to 80% of
+    /// their life span.
+    pub partly_tracked: usize,
+    /// The number of ground truth tracks tracked for less than 20% of
+    /// their life span.
+    pub mostly_lost: usize,
+    /// The sum of IoU of true positives.
+    pub similarity_sum: f64,
+}
+
+impl ClearMetrics {
+    /// Multi-object tracking accuracy.
+    pub fn mota(&self) -> f64 {
+        let tp = self.true_positives as f64;
+        let fp = self.false_positives as f64;
+        let idsw = self.id_switches as f64;
+        (tp - fp - idsw) / self.num_gt_dets()
+    }
+
+    /// Multi-object detection accuracy, which is MOTA without ID
+    /// switches.
+    pub fn moda(&self) -> f64 {
+        let tp = self.true_positives as f64;
+        let fp = self.false_positives as f64;
+        (tp - fp) / self.num_gt_dets()
+    }
+
+    /// Multi-object tracking precision, the mean IoU of true
+    /// positives.
+    pub fn motp(&self) -> f64 {
+        self.similarity_sum / (self.true_positives.max(1) as f64)
+    }
+
+    /// Soft MOTA, which weights true positives by their IoU.
+    pub fn smota(&self) -> f64 {
+        let fp = self.false_positives as f64;
+        let idsw = self.id_switches as f64;
+        (self.similarity_sum - fp - idsw) / self.num_gt_dets()
+    }
+
+    pub fn recall(&self) -> f64 {
+        self.true_positives as f64 / self.num_gt_dets()
+    }
+
+    pub fn precision(&self) -> f64 {
+        let num_tracker_dets = (self.true_positives + self.false_positives).max(1);
+        self.true_positives as f64 / num_tracker_dets as f64
+    }
+
+    fn num_gt_dets(&self) -> f64 {
+        (self.true_positives + self.false_negatives).max(1) as f64
+    }
+
+    fn combine(results: &[ClearMetrics]) -> Self {
+        results.iter().fold(Self::default(), |sum, result| Self {
+            true_positives: sum.true_positives + result.true_positives,
+            false_positives: sum.false_positives + result.false_positives,
+            false_negatives: sum.false_negatives + result.false_negatives,
+            id_switches: sum.id_switches + result.id_switches,
+            fragmentations: sum.fragmentations + result.fragmentations,
+            mostly_tracked: sum.mostly_tracked + result.mostly_tracked,
+            partly_tracked: sum.partly_tracked + result.partly_tracked,
+            mostly_lost: sum.mostly_lost + result.mostly_lost,
+            similarity_sum: sum.similarity_sum + result.similarity_sum,
+        })
+    }
+}
+
+/// The HOTA metrics at each of the [alphas()] thresholds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotaMetrics {
+    pub true_positives: [usize; NUM_ALPHAS],
+    pub false_positives: [usize; NUM_ALPHAS],
+    pub false_negatives: [usize; NUM_ALPHAS],
+    pub ass_a: [f64; NUM_ALPHAS],
+    pub ass_re: [f64; NUM_ALPHAS],
+    pub ass_pr: [f64; NUM_ALPHAS],
+    pub loc_a: [f64; NUM_ALPHAS],
+}
+
+impl HotaMetrics {
+    /// Detection accuracy at each threshold.
+    pub fn det_a_per_alpha(&self) -> [f64; NUM_ALPHAS] {
+        std::array::from_fn(|idx| {
+            let tp = self.true_positives[idx];
+            let total = tp + self.false_negatives[idx] + self.false_positives[idx];
+            tp as f64 / total.max(1) as f64
+        })
+    }
+
+    /// Detection recall at each threshold.
+    pub fn det_re_per_alpha(&self) -> [f64; NUM_ALPHAS] {
+        std::array::from_fn(|idx| {
+            let tp = self.true_positives[idx];
+            tp as f64 / (tp + self.false_negatives[idx]).max(1) as f64
+        })
+    }
+
+    /// Detection precision at each threshold.
+    pub fn det_pr_per_alpha(&self) -> [f64; NUM_ALPHAS] {
+        std::array::from_fn(|idx| {
+            let tp = self.true_positives[idx];
+            tp as f64 / (tp + self.false_positives[idx]).max(1) as f64
+        })
+    }
+
+    /// HOTA at each threshold.
+    pub fn hota_per_alpha(&self) -> [f64; NUM_ALPHAS] {
+        let det_a = self.det_a_per_alpha();
+        std::array::from_fn(|idx| (det_a[idx] * self.ass_a[idx]).sqrt())
+    }
+
+    /// HOTA averaged over thresholds.
+    pub fn hota(&self) -> f64 {
+        mean(&self.hota_per_alpha())
+    }
+
+    /// Detection accuracy averaged over thresholds.
+    pub fn det_a(&self) -> f64 {
+        mean(&self.det_a_per_alpha())
+    }
+
+    /// Association accuracy averaged over thresholds.
+    pub fn ass_a(&self) -> f64 {
+        mean(&self.ass_a)
+    }
+
+    /// Localization accuracy averaged over thresholds.
+    pub fn loc_a(&self) -> f64 {
+        mean(&self.loc_a)
+    }
+
+    fn empty() -> Self {
+        Self {
+            true_positives: [0; NUM_ALPHAS],
+            false_positives: [0; NUM_ALPHAS],
+            false_negatives: [0; NUM_ALPHAS],
+            ass_a: [0.0; NUM_ALPHAS],
+            ass_re: [0.0; NUM_ALPHAS],
+            ass_pr: [0.0; NUM_ALPHAS],
+            loc_a: [1.0; NUM_ALPHAS],
+        }
+    }
+
+    fn combine(results: &[HotaMetrics]) -> Self {
+        let sum_counts = |get: fn(&HotaMetrics) -> &[usize; NUM_ALPHAS]| {
+            std::array::from_fn(|idx| results.iter().map(|result| get(result)[idx]).sum())
+        };
+        let true_positives: [usize; NUM_ALPHAS] = sum_counts(|result| &result.true_positives);
+
+        // Association and localization scores are weighted by the
+        // number of true positives.
+        let weighted_sum = |get: fn(&HotaMetrics) -> &[f64; NUM_ALPHAS], idx: usize| -> f64 {
+            results
+                .iter()
+                .map(|result| get(result)[idx] * result.true_positives[idx] as f64)
+                .sum()
+        };
+        let weighted_av = |get: fn(&HotaMetrics) -> &[f64; NUM_ALPHAS]| {
+            std::array::from_fn(|idx| weighted_sum(get, idx) / true_positives[idx].max(1) as f64)
+        };
+
+        Self {
+            true_positives,
+            false_positives: sum_counts(|result| &result.false_positives),
+            false_negatives: sum_counts(|result| &result.false_negatives),
+            ass_a: weighted_av(|result| &result.ass_a),
+            ass_re: weighted_av(|result| &result.ass_re),
+            ass_pr: weighted_av(|result| &result.ass_pr),
+            loc_a: std::array::from_fn(|idx| {
+                weighted_sum(|result| &result.loc_a, idx).max(1e-10)
+                    / (true_positives[idx] as f64).max(1e-10)
+            }),
+        }
+    }
+}
+
+/// The evaluation result of one class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassResult {
+    pub class: Class,
+    pub clear: ClearMetrics,
+    pub hota: HotaMetrics,
+}
+
+/// The evaluation result of all classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalResult {
+    pub classes: Vec<ClassResult>,
+}
+
+impl EvalResult {
+    pub fn class(&self, class: &Class) -> Option<&ClassResult> {
+        self.classes.iter().find(|result| &result.class == class)
+    }
+}
+
+/// Evaluates tracker outputs against the ground truth. Each item is a
+/// pair of the ground truth labels and tracker outputs of one
+/// sequence. The metrics are accumulated over sequences.
+pub fn evaluate<I, G, T>(sequences: I) -> EvalResult
+where
+    I: IntoIterator<Item = (G, T)>,
+    G: AsRef<[Label]>,
+    T: AsRef<[Label]>,
+{
+    let sequences: Vec<(G, T)> = sequences.into_iter().collect();
+
+    let classes = EVAL_CLASSES
+        .iter()
+        .map(|class| {
+            let (clear, hota): (Vec<_>, Vec<_>) = sequences
+                .iter()
+                .map(|(gt, tracker)| {
+                    let data = SequenceData::new(gt.as_ref(), tracker.as_ref(), class);
+                    (eval_clear(&data), eval_hota(&data))
+                })
+                .unzip();
+
+            ClassResult {
+                class: class.clone(),
+                clear: ClearMetrics::combine(&clear),
+                hota: HotaMetrics::combine(&hota),
+            }
+        })
+        .collect();
+
+    EvalResult { classes }
+}
+
+/// The boxes of one frame after preprocessing.
+struct Timestep {
+    gt_ids: Vec<usize>,
+    tracker_ids: Vec<usize>,
+    /// The IoU matrix of ground truth by tracker boxes.
+    similarity: Vec<Vec<f64>>,
+}
+
+/// A sequence preprocessed for one class, where track IDs are
+/// relabeled to contiguous indices.
+struct SequenceData {
+    timesteps: Vec<Timestep>,
+    num_gt_ids: usize,
+    num_tracker_ids: usize,
+    num_gt_dets: usize,
+    num_tracker_dets: usize,
+}
+
+impl SequenceData {
+    fn new(gt: &[Label], tracker: &[Label], class: &Class) -> Self {
+        let mut frames: BTreeMap<u32, (Vec<&Label>, Vec<&Label>)> = BTreeMap::new();
+        for label in gt {
+            frames.entry(label.frame).or_default().0.push(label);
+        }
+        for label in tracker {
+            frames.entry(label.frame).or_default().1.push(label);
+        }
+
+        let mut gt_id_map = HashMap::new();
+        let mut tracker_id_map = HashMap::new();
+        let mut num_gt_dets = 0;
+        let mut num_tracker_dets = 0;
+
+        let timesteps = frames
+            .values()
+            .map(|(gt, tracker)| {
+                let (gt, tracker, similarity) = preprocess_frame(gt, tracker, class);
+                num_gt_dets += gt.len();
+                num_tracker_dets += tracker.len();
+
+                let relabel = |map: &mut HashMap<u32, usize>, id: u32| {
+                    let next = map.len();
+                    *map.entry(id).or_insert(next)
+                };
+                let gt_ids = gt
+                    .iter()
+                    .map(|label| relabel(&mut gt_id_map, label.track_id.unwrap()))
+                    .collect();
+                let tracker_ids = tracker
+                    .iter()
+                    .map(|label| relabel(&mut tracker_id_map, label.track_id.unwrap()))
+                    .collect();
+
+                Timestep {
+                    gt_ids,
+                    tracker_ids,
+                    similarity,
+                }
+            })
+            .collect();
+
+        Self {
+            timesteps,
+            num_gt_ids: gt_id_map.len(),
+            num_tracker_ids: tracker_id_map.len(),
+            num_gt_dets,
+            num_tracker_dets,
+        }
+    }
+}
+
+/// Filters the ground truth and tracker boxes of a frame. It returns
+/// the kept boxes and their IoU matrix.
+fn preprocess_frame<'a>(
+    gt: &[&'a Label],
+    tracker: &[&'a Label],
+    class: &Class,
+) -> (Vec<&'a Label>, Vec<&'a Label>, Vec<Vec<f64>>) {
+    let dont_care: Vec<_> = gt
+        .iter()
+        .filter(|label| label.class == Class::DontCare)
+        .map(|label| &label.bbox)
+        .collect();
+    let gt: Vec<&Label> = gt
+        .iter()
+        .copied()
+        .filter(|label| label.track_id.is_some())
+        .filter(|label| &label.class == class || is_distractor(class, &label.class))
+        .collect();
+    let tracker: Vec<&Label> = tracker
+        .iter()
+        .copied()
+        .filter(|label| label.track_id.is_some() && &label.class == class)
+        .collect();
+    let similarity: Vec<Vec<f64>> = gt
+        .iter()
+        .map(|gt| tracker.iter().map(|tr| gt.bbox.iou(&tr.bbox)).collect())
+        .collect();
+
+    let is_evaluated = |label: &Label| {
+        &label.class == class
+            && occlusion_level(label) <= MAX_OCCLUSION
+            && truncation_level(label) <= MAX_TRUNCATION
+    };
+
+    // Remove tracker boxes matched to ground truth boxes that are not
+    // evaluated.
+    let mut removed = vec![false; tracker.len()];
+    let mut matched = vec![false; tracker.len()];
+    let cost: Vec<Vec<f64>> = similarity
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&iou| {
+                    if iou < MATCH_THRESHOLD - EPSILON {
+                        0.0
+                    } else {
+                        -iou
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for (row, col) in linear_sum_assignment(&cost) {
+        if -cost[row][col] <= EPSILON {
+            continue;
+        }
+        matched[col] = true;
+        if !is_evaluated(gt[row]) {
+            removed[col] = true;
+        }
+    }
+
+    // Remove small unmatched tracker boxes and those in DontCare
+    // areas.
+    for (idx, label) in tracker.iter().enumerate() {
+        if matched[idx] {
+            continue;
+        }
+        let height = label.bbox.ymax - label.bbox.ymin;
+        let in_dont_care = dont_care
+            .iter()
+            .any(|area| bbox_ioa(&label.bbox, area) > 0.5 + EPSILON);
+        if height <= MIN_HEIGHT + EPSILON || in_dont_care {
+            removed[idx] = true;
+        }
+    }
+
+    let kept_gt: Vec<usize> = (0..gt.len()).filter(|&idx| is_evaluated(gt[idx])).collect();
+    let kept_tracker: Vec<usize> = (0..tracker.len()).filter(|&idx| !removed[idx]).collect();
+    let similarity = kept_gt
+        .iter()
+        .map(|&row| {
+            kept_tracker
+                .iter()
+                .map(|&col| similarity[row][col])
+                .collect()
+        })
+        .collect();
+
+    (
+        kept_gt.iter().map(|&idx| gt[idx]).collect(),
+        kept_tracker.iter().map(|&idx| tracker[idx]).collect(),
+        similarity,
+    )
+}
+
+fn eval_clear(data: &SequenceData) -> ClearMetrics {
+    let mut metrics = ClearMetrics::default();
+
+    let mut gt_id_count = vec![0usize; data.num_gt_ids];
+    let mut gt_matched_count = vec![0usize; data.num_gt_ids];
+    let mut gt_frag_count = vec![0usize; data.num_gt_ids];
+    // The last matched tracker ID of each ground truth track
+    let mut prev_tracker_id: Vec<Option<usize>> = vec![None; data.num_gt_ids];
+    // The tracker ID matched in the previous frame
+    let mut prev_timestep_tracker_id: Vec<Option<usize>> = vec![None; data.num_gt_ids];
+
+    for step in &data.timesteps {
+        let Timestep {
+            gt_ids,
+            tracker_ids,
+            similarity,
+        } = step;
+
+        if gt_ids.is_empty() {
+            metrics.false_positives += tracker_ids.len();
+            continue;
+        }
+        if tracker_ids.is_empty() {
+            metrics.false_negatives += gt_ids.len();
+            for &id in gt_ids {
+                gt_id_count[id] += 1;
+            }
+            continue;
+        }
+
+        // Prefer matches that continue the previous frame.
+        let score: Vec<Vec<f64>> = gt_ids
+            .iter()
+            .zip(similarity)
+            .map(|(&gt_id, row)| {
+                tracker_ids
+                    .iter()
+                    .zip(row)
+                    .map(|(&tracker_id, &iou)| {
+                        if iou < MATCH_THRESHOLD - EPSILON {
+                            0.0
+                        } else if prev_timestep_tracker_id[gt_id] == Some(tracker_id) {
+                            1000.0 + iou
+                        } else {
+                            iou
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let matches: Vec<(usize, usize)> = assign_max(&score)
+            .into_iter()
+            .filter(|&(row, col)| score[row][col] > EPSILON)
+            .collect();
+
+        for &(row, col) in &matches {
+            let gt_id = gt_ids[row];
+            let tracker_id = tracker_ids[col];
+            if prev_tracker_id[gt_id].is_some_and(|prev| prev != tracker_id) {
+                metrics.id_switches += 1;
+            }
+        }
+
+        for &id in gt_ids {
+            gt_id_count[id] += 1;
+        }
+        let not_previously_tracked: Vec<bool> = prev_timestep_tracker_id
+            .iter()
+            .map(Option::is_none)
+            .collect();
+        prev_timestep_tracker_id.fill(None);
+        for &(row, col) in &matches {
+            let gt_id = gt_ids[row];
+            gt_matched_count[gt_id] += 1;
+            prev_tracker_id[gt_id] = Some(tracker_ids[col]);
+            prev_timestep_tracker_id[gt_id] = Some(tracker_ids[col]);
+            if not_previously_tracked[gt_id] {
+                gt_frag_count[gt_id] += 1;
+            }
+            metrics.similarity_sum += similarity[row][col];
+        }
+
+        let num_matches = matches.len();
+        metrics.true_positives += num_matches;
+        metrics.false_negatives += gt_ids.len() - num_matches;
+        metrics.false_positives += tracker_ids.len() - num_matches;
+    }
+
+    for (&count, &matched) in gt_id_count.iter().zip(&gt_matched_count) {
+        if count == 0 {
+            continue;
+        }
+        let ratio = matched as f64 / count as f64;
+        if ratio > 0.8 {
+            metrics.mostly_tracked += 1;
+        } else if ratio >= 0.2 {
+            metrics.partly_tracked += 1;
+        }
+    }
+    metrics.mostly_lost = data.num_gt_ids - metrics.mostly_tracked - metrics.partly_tracked;
+    metrics.fragmentations = gt_frag_count
+        .iter()
+        .map(|&count| count.saturating_sub(1))
+        .sum();
+
+    metrics
+}
+
+fn eval_hota(data: &SequenceData) -> HotaMetrics {
+    let mut metrics = HotaMetrics::empty();
+
+    if data.num_tracker_dets == 0 {
+        metrics.false_negatives = [data.num_gt_dets; NUM_ALPHAS];
+        return metrics;
+    }
+    if data.num_gt_dets == 0 {
+        metrics.false_positives = [data.num_tracker_dets; NUM_ALPHAS];
+        return metrics;
+    }
+
+    // Accumulate the soft association between track pairs.
+    let mut potential_matches = vec![vec![0.0; data.num_tracker_ids]; data.num_gt_ids];
+    let mut gt_id_count = vec![0.0; data.num_gt_ids];
+    let mut tracker_id_count = vec![0.0; data.num_tracker_ids];
+
+    for step in &data.timesteps {
+        let Timestep {
+            gt_ids,
+            tracker_ids,
+            similarity,
+        } = step;
+
+        let row_sums: Vec<f64> = similarity.iter().map(|row| row.iter().sum()).collect();
+        let col_sums: Vec<f64> = (0..tracker_ids.len())
+            .map(|col| similarity.iter().map(|row| row[col]).sum())
+            .collect();
+
+        for (row, &gt_id) in gt_ids.iter().enumerate() {
+            for (col, &tracker_id) in tracker_ids.iter().enumerate() {
+                let sim = similarity[row][col];
+                let denom = row_sums[row] + col_sums[col] - sim;
+                if denom > EPSILON {
+                    potential_matches[gt_id][tracker_id] += sim / denom;
+                }
+            }
+        }
+        for &id in gt_ids {
+            gt_id_count[id] += 1.0;
+        }
+        for &id in tracker_ids {
+            tracker_id_count[id] += 1.0;
+        }
+    }
+
+    let global_alignment: Vec<Vec<f64>> = potential_matches
+        .iter()
+        .zip(&gt_id_count)
+        .map(|(row, &gt_count)| {
+            row.iter()
+                .zip(&tracker_id_count)
+                .map(|(&potential, &tracker_count)| {
+                    potential / (gt_count + tracker_count - potential)
+                })
+                .collect()
+        })
+        .collect();
+
+    let alphas = alphas();
+    let mut matches_counts =
+        vec![vec![vec![0.0; data.num_tracker_ids]; data.num_gt_ids]; NUM_ALPHAS];
+    let mut loc_sum = [0.0; NUM_ALPHAS];
+
+    for step in &data.timesteps {
+        let Timestep {
+            gt_ids,
+            tracker_ids,
+            similarity,
+        } = step;
+
+        if gt_ids.is_empty() || tracker_ids.is_empty() {
+            for idx in 0..NUM_ALPHAS {
+                metrics.false_positives[idx] += tracker_ids.len();
+                metrics.false_negatives[idx] += gt_ids.len();
+            }
+            continue;
+        }
+
+        let score: Vec<Vec<f64>> = gt_ids
+            .iter()
+            .zip(similarity)
+            .map(|(&gt_id, row)| {
+                tracker_ids
+                    .iter()
+                    .zip(row)
+                    .map(|(&tracker_id, &sim)| global_alignment[gt_id][tracker_id] * sim)
+                    .collect()
+            })
+            .collect();
+        let matches = assign_max(&score);
+
+        for (idx, &alpha) in alphas.iter().enumerate() {
+            let mut num_matches = 0;
+            for &(row, col) in &matches {
+                let sim = similarity[row][col];
+                if sim < alpha - EPSILON {
+                    continue;
+                }
+                num_matches += 1;
+                loc_sum[idx] += sim;
+                matches_counts[idx][gt_ids[row]][tracker_ids[col]] += 1.0;
+            }
+
+            metrics.true_positives[idx] += num_matches;
+            metrics.false_negatives[idx] += gt_ids.len() - num_matches;
+            metrics.false_positives[idx] += tracker_ids.len() - num_matches;
+        }
+    }
+
+    for (idx, matches_count) in matches_counts.iter().enumerate() {
+        let mut ass_a = 0.0;
+        let mut ass_re = 0.0;
+        let mut ass_pr = 0.0;
+
+        for (row, &gt_count) in matches_count.iter().zip(&gt_id_count) {
+            for (&count, &tracker_count) in row.iter().zip(&tracker_id_count) {
+                if count == 0.0 {
+                    continue;
+                }
+                ass_a += count * count / (gt_count + tracker_count - count).max(1.0);
+                ass_re += count * count / gt_count.max(1.0);
+                ass_pr += count * count / tracker_count.max(1.0);
+            }
+        }
+
+        let tp = metrics.true_positives[idx].max(1) as f64;
+        metrics.ass_a[idx] = ass_a / tp;
+        metrics.ass_re[idx] = ass_re / tp;
+        metrics.ass_pr[idx] = ass_pr / tp;
+        metrics.loc_a[idx] =
+            loc_sum[idx].max(1e-10) / (metrics.true_positives[idx] as f64).max(1e-10);
+    }
+
+    metrics
+}
+
+/// Finds the assignment that maximizes the total score.
+fn assign_max(score: &[Vec<f64>]) -> Vec<(usize, usize)> {
+    let cost: Vec<Vec<f64>> = score
+        .iter()
+        .map(|row| row.iter().map(|value| -value).collect())
+        .collect();
+    linear_sum_assignment(&cost)
+}
+
+fn is_distractor(class: &Class, other: &Class) -> bool {
+    match class {
+        Class::Car => other == &Class::Van,
+        Class::Pedestrian => {
+            matches!(other, Class::PersonSitting | Class::Person)
+        }
+        _ => false,
+    }
+}
+
+fn occlusion_level(label: &Label) -> i32 {
+    label.occlusion.map(|occ| occ as i32).unwrap_or(-1)
+}
+
+fn truncation_level(label: &Label) -> f64 {
+    match label.truncation {
+        Some(Truncation::Labeled(value)) => value.raw(),
+        Some(Truncation::Ignored) => 2.0,
+        None => -1.0,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
@@ -1,4 +1,5 @@
 use crate::{
+    object::geometry::{box_center, box_corners, box_edges, box_faces},
     serde::{occlusion, tracking_truncation},
     Error,
 };
@@ -12,7 +13,7 @@ use std::{
     path::Path,
 };
 
-pub use crate::object::{BoundingBox, Extents, Location, Occlusion};
+pub use crate::object::{BoundingBox, Class, Extents, Location, Occlusion};
 
 pub type LabelFromReaderIter<R> = csv::DeserializeRecordsIntoIter<R, Label>;
 pub type LabelFromPathIter = LabelFromReaderIter<BufReader<File>>;
@@ -23,7 +24,7 @@ pub type LabelFromStrIter<'a> = LabelFromReaderIter<Cursor<&'a str>>;
 pub struct Label {
     pub frame: u32,
     pub track_id: Option<u32>,
-    pub class: String,
+    pub class: Class,
     pub truncation: Option<Truncation>,
     pub occlusion: Option<Occlusion>,
     pub alpha: Angle,
@@ -37,7 +38,7 @@ pub struct Label {
 struct SerializedLabel {
     pub frame: u32,
     pub track_id: i32,
-    pub class: String,
+    pub class: Class,
     #[serde(with = "tracking_truncation")]
     pub truncation: Option<Truncation>,
     #[serde(with = "occlusion")]
@@ -162,6 +163,30 @@ impl From<Label> for SerializedLabel {
 }
 
 impl Label {
+    /// Computes the eight box corners in camera coordinates in
+    /// meters. See [object::Label::corners](crate::object::Label::corners)
+    /// for the corner order.
+    pub fn corners(&self) -> [[f64; 3]; 8] {
+        box_corners(&self.extents, &self.location, self.rotation_y)
+    }
+
+    /// Computes the box center in camera coordinates in meters.
+    pub fn center(&self) -> [f64; 3] {
+        box_center(&self.extents, &self.location)
+    }
+
+    /// Gets the corners of each face in the order of
+    /// [BOX_FACES](crate::object::BOX_FACES).
+    pub fn faces(&self) -> [[[f64; 3]; 4]; 6] {
+        box_faces(&self.corners())
+    }
+
+    /// Gets the end points of each edge in the order of
+    /// [BOX_EDGES](crate::object::BOX_EDGES).
+    pub fn edges(&self) -> [[[f64; 3]; 2]; 12] {
+        box_edges(&self.corners())
+    }
+
     pub fn iter_from_reader<R>(reader: R) -> LabelFromReaderIter<R>
     where
         R: Read,
@@ -1,9 +1,12 @@
 mod iter;
+mod pose;
 mod types;
 mod vec;
 mod write;
 
+pub use pose::PoseFrame;
 pub use types::*;
+pub use write::OxtsFloatFormat;
 // pub use iter::*;
 // pub use vec::*;
 // pub use write::*;
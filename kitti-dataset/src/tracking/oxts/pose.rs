@@ -0,0 +1,148 @@
+use super::Oxts;
+use crate::odometry::{
+    pose::{affine_compose, affine_inverse, Affine},
+    Pose,
+};
+use std::borrow::Borrow;
+
+/// The earth radius used by the devkit Mercator projection in meters.
+const EARTH_RADIUS: f64 = 6378137.0;
+
+/// The WGS84 flattening.
+const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+
+/// The coordinate frame of the poses converted from OXTS packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PoseFrame {
+    /// The devkit convention. Positions are projected with a Mercator
+    /// projection scaled at the latitude of the first packet, and each
+    /// pose is expressed relative to the first pose, which becomes the
+    /// identity.
+    #[default]
+    FirstPose,
+    /// A local east-north-up frame located at the first packet.
+    /// Positions are converted through WGS84 ECEF coordinates, and
+    /// orientations are kept in the ENU axes, so the first pose
+    /// carries the initial roll, pitch and heading.
+    LocalEnu,
+}
+
+impl Oxts {
+    /// Converts a sequence of OXTS packets into IMU-to-world poses.
+    ///
+    /// The rotation is `Rz(yaw) * Ry(pitch) * Rx(roll)` as in the
+    /// devkit `convertOxtsToPose.m`. The output has the same length and
+    /// order as the input.
+    ///
+    /// ```ignore
+    /// let oxts = Oxts::vec_from_path("oxts/0000.txt")?;
+    /// let poses = Oxts::poses(&oxts, PoseFrame::FirstPose);
+    /// ```
+    pub fn poses<I, A>(packets: I, frame: PoseFrame) -> Vec<Pose>
+    where
+        I: IntoIterator<Item = A>,
+        A: Borrow<Oxts>,
+    {
+        let mut packets = packets.into_iter().peekable();
+        let Some(first) = packets.peek() else {
+            return vec![];
+        };
+        let first = first.borrow();
+
+        match frame {
+            PoseFrame::FirstPose => {
+                let scale = first.position.lat.as_radians().cos();
+                let first_inv = affine_inverse(&mercator_pose(first, scale));
+                packets
+                    .map(|oxts| {
+                        let pose = mercator_pose(oxts.borrow(), scale);
+                        Pose::from_affine(&affine_compose(&first_inv, &pose))
+                    })
+                    .collect()
+            }
+            PoseFrame::LocalEnu => {
+                let origin = GeoOrigin::new(first);
+                packets
+                    .map(|oxts| {
+                        let oxts = oxts.borrow();
+                        Pose::from_affine(&with_translation(rotation(oxts), origin.to_enu(oxts)))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn mercator_pose(oxts: &Oxts, scale: f64) -> Affine {
+    let lat = oxts.position.lat.as_degrees();
+    let lon = oxts.position.lon.as_degrees();
+    let x = scale * lon.to_radians() * EARTH_RADIUS;
+    let y = scale * EARTH_RADIUS * ((90.0 + lat).to_radians() / 2.0).tan().ln();
+    let z = oxts.position.alt.as_meters();
+    with_translation(rotation(oxts), [x, y, z])
+}
+
+fn rotation(oxts: &Oxts) -> [[f64; 3]; 3] {
+    let (sr, cr) = oxts.rotation.roll.as_radians().sin_cos();
+    let (sp, cp) = oxts.rotation.pitch.as_radians().sin_cos();
+    let (sy, cy) = oxts.rotation.yaw.as_radians().sin_cos();
+
+    [
+        [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+        [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+        [-sp, cp * sr, cp * cr],
+    ]
+}
+
+fn with_translation(rotation: [[f64; 3]; 3], translation: [f64; 3]) -> Affine {
+    let mut affine = [[0.0; 4]; 3];
+    for (row, (rot, t)) in affine.iter_mut().zip(rotation.iter().zip(translation)) {
+        row[..3].copy_from_slice(rot);
+        row[3] = t;
+    }
+    affine
+}
+
+/// The ECEF position and orientation of a local ENU frame.
+struct GeoOrigin {
+    ecef: [f64; 3],
+    /// The rows are the east, north and up axes in ECEF.
+    axes: [[f64; 3]; 3],
+}
+
+impl GeoOrigin {
+    fn new(oxts: &Oxts) -> Self {
+        let (slat, clat) = oxts.position.lat.as_radians().sin_cos();
+        let (slon, clon) = oxts.position.lon.as_radians().sin_cos();
+
+        Self {
+            ecef: to_ecef(oxts),
+            axes: [
+                [-slon, clon, 0.0],
+                [-slat * clon, -slat * slon, clat],
+                [clat * clon, clat * slon, slat],
+            ],
+        }
+    }
+
+    fn to_enu(&self, oxts: &Oxts) -> [f64; 3] {
+        let ecef = to_ecef(oxts);
+        let diff: [f64; 3] = std::array::from_fn(|idx| ecef[idx] - self.ecef[idx]);
+        self.axes
+            .map(|axis| axis.iter().zip(diff).map(|(a, d)| a * d).sum())
+    }
+}
+
+fn to_ecef(oxts: &Oxts) -> [f64; 3] {
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let (slat, clat) = oxts.position.lat.as_radians().sin_cos();
+    let (slon, clon) = oxts.position.lon.as_radians().sin_cos();
+    let alt = oxts.position.alt.as_meters();
+    let n = EARTH_RADIUS / (1.0 - e2 * slat * slat).sqrt();
+
+    [
+        (n + alt) * clat * clon,
+        (n + alt) * clat * slon,
+        (n * (1.0 - e2) + alt) * slat,
+    ]
+}
@@ -1,8 +1,9 @@
 use super::Oxts;
-use crate::error::Error;
+use crate::{common::indexed_files, error::Error};
 use std::{
+    collections::BTreeMap,
     fs::File,
-    io::{BufReader, Cursor, Read},
+    io::{self, BufReader, Cursor, Read},
     path::Path,
 };
 
@@ -31,4 +32,26 @@ impl Oxts {
         let reader = Cursor::new(text);
         Self::vec_from_reader(reader)
     }
+
+    /// Reads the raw data `oxts/data` directory with one packet per
+    /// file, keyed by the frame index in the file name. Files not
+    /// named by digits are ignored.
+    pub fn map_from_dir<P>(dir: P) -> Result<BTreeMap<usize, Oxts>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        indexed_files::list_indexed_files(dir.as_ref(), "txt")?
+            .into_iter()
+            .map(|(frame_idx, path)| {
+                let Some(oxts) = Self::vec_from_path(path)?.into_iter().next() else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "unexpected empty oxts file",
+                    )
+                    .into());
+                };
+                Ok((frame_idx, oxts))
+            })
+            .collect()
+    }
 }
@@ -1,25 +1,65 @@
-use super::Oxts;
+use super::{types::SerializedOxts, Oxts};
+use crate::float_format::format_general;
 use std::{
     borrow::Borrow,
-    fs::File,
+    fs::{self, File},
     io::{self, BufWriter, Write},
     path::Path,
 };
 
+/// The formatting of floating point values in written OXTS files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OxtsFloatFormat {
+    /// The `%.14g` format of the original files. Values read from
+    /// KITTI files are written back byte-for-byte, while values with
+    /// more significant digits are rounded.
+    #[default]
+    Devkit,
+    /// The shortest text that parses back to the same `f64` value.
+    Shortest,
+}
+
 impl Oxts {
+    /// Writes packets one per line in the tracking `oxts/XXXX.txt`
+    /// layout with the [OxtsFloatFormat::Devkit] format.
     pub fn write_to_writer<W, I, A>(writer: W, oxts: I) -> io::Result<()>
     where
         I: IntoIterator<Item = A>,
         W: Write,
         A: Borrow<Oxts>,
     {
-        let mut writer = csv::WriterBuilder::new()
-            .has_headers(false)
-            .delimiter(b' ')
-            .from_writer(writer);
+        Self::write_to_writer_with_format(writer, oxts, OxtsFloatFormat::Devkit)
+    }
 
+    pub fn write_to_path<P, I, A>(path: P, oxts: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        P: AsRef<Path>,
+        A: Borrow<Oxts>,
+    {
+        Self::write_to_path_with_format(path, oxts, OxtsFloatFormat::Devkit)
+    }
+
+    pub fn write_to_string<I, A>(oxts: I) -> io::Result<String>
+    where
+        I: IntoIterator<Item = A>,
+        A: Borrow<Oxts>,
+    {
+        Self::write_to_string_with_format(oxts, OxtsFloatFormat::Devkit)
+    }
+
+    pub fn write_to_writer_with_format<W, I, A>(
+        mut writer: W,
+        oxts: I,
+        format: OxtsFloatFormat,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        W: Write,
+        A: Borrow<Oxts>,
+    {
         for record in oxts {
-            writer.serialize(record.borrow())?;
+            write_line(&mut writer, record.borrow(), format)?;
         }
 
         writer.flush()?;
@@ -27,23 +67,120 @@ impl Oxts {
         Ok(())
     }
 
-    pub fn write_to_path<P, I, A>(path: P, oxts: I) -> io::Result<()>
+    pub fn write_to_path_with_format<P, I, A>(
+        path: P,
+        oxts: I,
+        format: OxtsFloatFormat,
+    ) -> io::Result<()>
     where
         I: IntoIterator<Item = A>,
         P: AsRef<Path>,
         A: Borrow<Oxts>,
     {
         let writer = BufWriter::new(File::create(path)?);
-        Self::write_to_writer(writer, oxts)
+        Self::write_to_writer_with_format(writer, oxts, format)
     }
 
-    pub fn write_to_string<I, A>(oxts: I) -> io::Result<String>
+    pub fn write_to_string_with_format<I, A>(oxts: I, format: OxtsFloatFormat) -> io::Result<String>
     where
         I: IntoIterator<Item = A>,
         A: Borrow<Oxts>,
     {
         let mut buf = vec![];
-        Self::write_to_writer(&mut buf, oxts)?;
+        Self::write_to_writer_with_format(&mut buf, oxts, format)?;
         Ok(String::from_utf8(buf).unwrap())
     }
+
+    /// Writes one file per packet in the raw data `oxts/data` layout,
+    /// which are named `0000000000.txt`, `0000000001.txt` and so on.
+    /// The directory is created if it does not exist.
+    pub fn write_to_dir<P, I, A>(dir: P, oxts: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        P: AsRef<Path>,
+        A: Borrow<Oxts>,
+    {
+        Self::write_to_dir_with_format(dir, oxts, OxtsFloatFormat::Devkit)
+    }
+
+    pub fn write_to_dir_with_format<P, I, A>(
+        dir: P,
+        oxts: I,
+        format: OxtsFloatFormat,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = A>,
+        P: AsRef<Path>,
+        A: Borrow<Oxts>,
+    {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        for (idx, record) in oxts.into_iter().enumerate() {
+            let path = dir.join(format!("{idx:010}.txt"));
+            Self::write_to_path_with_format(path, [record], format)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a packet in the devkit layout, where values are separated by
+/// spaces and each line ends with a trailing space.
+fn write_line<W>(mut writer: W, oxts: &Oxts, format: OxtsFloatFormat) -> io::Result<()>
+where
+    W: Write,
+{
+    let SerializedOxts {
+        lat,
+        lon,
+        alt,
+        roll,
+        pitch,
+        yaw,
+        vn,
+        ve,
+        vf,
+        vl,
+        vu,
+        ax,
+        ay,
+        az,
+        af,
+        al,
+        au,
+        wx,
+        wy,
+        wz,
+        wf,
+        wl,
+        wu,
+        posacc,
+        velacc,
+        navstat,
+        numsats,
+        posmode,
+        velmode,
+        orimode,
+    } = oxts.clone().into();
+
+    let floats = [
+        lat, lon, alt, roll, pitch, yaw, vn, ve, vf, vl, vu, ax, ay, az, af, al, au, wx, wy, wz,
+        wf, wl, wu, posacc, velacc,
+    ];
+    for value in floats {
+        match format {
+            OxtsFloatFormat::Devkit => write!(writer, "{} ", format_general(value, 14))?,
+            OxtsFloatFormat::Shortest => write!(writer, "{value} ")?,
+        }
+    }
+
+    let mode = |mode: Option<u8>| mode.map_or(-1, i16::from);
+    writeln!(
+        writer,
+        "{navstat} {numsats} {} {} {} ",
+        mode(posmode),
+        mode(velmode),
+        mode(orimode)
+    )
 }
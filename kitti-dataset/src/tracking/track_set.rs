@@ -0,0 +1,181 @@
+use super::{Class, Label};
+use crate::Error;
+use std::{collections::BTreeMap, ops::RangeInclusive, path::Path};
+
+/// Tracking labels of a sequence grouped by tracks and by frames.
+///
+/// Labels without a track ID, such as DontCare areas, do not belong
+/// to any track and are available from [TrackSet::untracked()].
+///
+/// ```ignore
+/// let set = TrackSet::from_path("label_02/0000.txt")?;
+/// for track in set.track_iter() {
+///     println!("{} {} {:?}", track.id(), track.class(), track.frame_range());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackSet {
+    labels: Vec<Label>,
+    /// Label indices of each track sorted by frame.
+    tracks: BTreeMap<u32, Vec<usize>>,
+    /// Label indices of each frame in the file order.
+    frames: BTreeMap<u32, Vec<usize>>,
+    untracked: Vec<usize>,
+}
+
+impl TrackSet {
+    pub fn new(labels: Vec<Label>) -> Self {
+        let mut tracks: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        let mut frames: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        let mut untracked = vec![];
+
+        for (idx, label) in labels.iter().enumerate() {
+            frames.entry(label.frame).or_default().push(idx);
+
+            match label.track_id {
+                Some(id) => tracks.entry(id).or_default().push(idx),
+                None => untracked.push(idx),
+            }
+        }
+
+        for indices in tracks.values_mut() {
+            indices.sort_by_key(|&idx| labels[idx].frame);
+        }
+
+        Self {
+            labels,
+            tracks,
+            frames,
+            untracked,
+        }
+    }
+
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self::new(Label::vec_from_path(path)?))
+    }
+
+    /// Gets all labels in the original order.
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn into_labels(self) -> Vec<Label> {
+        self.labels
+    }
+
+    pub fn num_tracks(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Gets the track IDs in ascending order.
+    pub fn track_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.tracks.keys().copied()
+    }
+
+    pub fn track(&self, id: u32) -> Option<Track<'_>> {
+        let indices = self.tracks.get(&id)?;
+        Some(Track {
+            set: self,
+            id,
+            indices,
+        })
+    }
+
+    /// Iterates over tracks in ascending order of track IDs.
+    pub fn track_iter(&self) -> impl Iterator<Item = Track<'_>> {
+        self.tracks.iter().map(|(&id, indices)| Track {
+            set: self,
+            id,
+            indices,
+        })
+    }
+
+    /// Gets the frame numbers having labels in ascending order.
+    pub fn frame_numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.frames.keys().copied()
+    }
+
+    /// Gets all labels in a frame, including untracked labels.
+    pub fn frame(&self, frame: u32) -> impl Iterator<Item = &Label> {
+        self.frames
+            .get(&frame)
+            .into_iter()
+            .flatten()
+            .map(|&idx| &self.labels[idx])
+    }
+
+    /// Gets the labels without track IDs.
+    pub fn untracked(&self) -> impl Iterator<Item = &Label> {
+        self.untracked.iter().map(|&idx| &self.labels[idx])
+    }
+}
+
+impl From<Vec<Label>> for TrackSet {
+    fn from(labels: Vec<Label>) -> Self {
+        Self::new(labels)
+    }
+}
+
+impl FromIterator<Label> for TrackSet {
+    fn from_iter<T: IntoIterator<Item = Label>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// The labels of one track sorted by frame.
+#[derive(Debug, Clone)]
+pub struct Track<'a> {
+    set: &'a TrackSet,
+    id: u32,
+    indices: &'a [usize],
+}
+
+impl<'a> Track<'a> {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Gets the number of labels in the track. It is less than the
+    /// frame range if the object is missing in some frames.
+    pub fn num_labels(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn first_frame(&self) -> u32 {
+        self.first().frame
+    }
+
+    pub fn last_frame(&self) -> u32 {
+        self.set.labels[*self.indices.last().unwrap()].frame
+    }
+
+    pub fn frame_range(&self) -> RangeInclusive<u32> {
+        self.first_frame()..=self.last_frame()
+    }
+
+    /// Gets the class of the first label in the track.
+    pub fn class(&self) -> &'a Class {
+        &self.first().class
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &'a Label> + '_ {
+        self.indices.iter().map(|&idx| &self.set.labels[idx])
+    }
+
+    /// Gets the label in a frame.
+    pub fn label_at(&self, frame: u32) -> Option<&'a Label> {
+        let labels = &self.set.labels;
+        let pos = self
+            .indices
+            .binary_search_by_key(&frame, |&idx| labels[idx].frame)
+            .ok()?;
+        Some(&labels[self.indices[pos]])
+    }
+
+    fn first(&self) -> &'a Label {
+        &self.set.labels[self.indices[0]]
+    }
+}
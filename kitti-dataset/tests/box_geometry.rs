@@ -0,0 +1,122 @@
+use kitti_dataset::{
+    object::{self, BOX_EDGES, BOX_FACES},
+    tracking,
+};
+use measurements::{Angle, Length};
+
+const EPSILON: f64 = 1e-5;
+
+fn assert_points_eq<const N: usize>(lhs: &[[f64; 3]; N], rhs: &[[f64; 3]; N]) {
+    for (lp, rp) in lhs.iter().zip(rhs) {
+        for (lv, rv) in lp.iter().zip(rp) {
+            assert!((lv - rv).abs() < EPSILON, "{lhs:?} != {rhs:?}");
+        }
+    }
+}
+
+#[test]
+fn object_box_corners() {
+    let labels = object::Label::vec_from_path("tests/object_label.txt").unwrap();
+    let car = &labels[0];
+
+    let expect = [
+        [-1.519795, 1.71, 48.503630],
+        [0.149898, 1.71, 48.535699],
+        [0.219795, 1.71, 44.896370],
+        [-1.449898, 1.71, 44.864301],
+        [-1.519795, 0.06, 48.503630],
+        [0.149898, 0.06, 48.535699],
+        [0.219795, 0.06, 44.896370],
+        [-1.449898, 0.06, 44.864301],
+    ];
+    assert_points_eq(&car.corners(), &expect);
+    assert_points_eq(&[car.center()], &[[-0.65, 0.885, 46.70]]);
+
+    // The center is the mean of the corners.
+    for label in &labels {
+        let corners = label.corners();
+        let mut mean = [0.0; 3];
+        for corner in &corners {
+            for (sum, value) in mean.iter_mut().zip(corner) {
+                *sum += value / 8.0;
+            }
+        }
+        assert_points_eq(&[mean], &[label.center()]);
+    }
+}
+
+#[test]
+fn object_box_faces_and_edges() {
+    let labels = object::Label::vec_from_path("tests/object_label.txt").unwrap();
+    let label = &labels[1];
+    let corners = label.corners();
+
+    let faces = label.faces();
+    for (face, indices) in faces.iter().zip(BOX_FACES) {
+        assert_points_eq(face, &indices.map(|idx| corners[idx]));
+    }
+
+    // Every edge has the length of one of the extents.
+    let extents = [
+        label.extents.height.as_meters(),
+        label.extents.width.as_meters(),
+        label.extents.length.as_meters(),
+    ];
+    for ([p, q], _) in label.edges().iter().zip(BOX_EDGES) {
+        let len = p
+            .iter()
+            .zip(q)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(extents.iter().any(|ext| (ext - len).abs() < EPSILON));
+    }
+}
+
+#[test]
+fn object_box_labelled_faces() {
+    let labels = object::Label::vec_from_path("tests/object_label.txt").unwrap();
+    let mut label = labels[0].clone();
+    label.rotation_y = Angle::from_radians(0.0);
+    label.location.x = Length::from_meters(0.0);
+    label.location.y = Length::from_meters(0.0);
+    label.location.z = Length::from_meters(0.0);
+    let half_length = label.extents.length.as_meters() / 2.0;
+    let half_width = label.extents.width.as_meters() / 2.0;
+    let height = label.extents.height.as_meters();
+
+    // The heading is +x and the left side is +z in the camera frame.
+    let [front, right, back, left, bottom, top] = label.faces();
+    let all = |face: [[f64; 3]; 4], axis: usize, value: f64| {
+        face.iter()
+            .all(|corner| (corner[axis] - value).abs() < EPSILON)
+    };
+    assert!(all(front, 0, half_length));
+    assert!(all(right, 2, -half_width));
+    assert!(all(back, 0, -half_length));
+    assert!(all(left, 2, half_width));
+    assert!(all(bottom, 1, 0.0));
+    assert!(all(top, 1, -height));
+}
+
+#[test]
+fn tracking_box_corners() {
+    let labels = tracking::Label::vec_from_path("tests/tracking_label.txt").unwrap();
+    let label = labels
+        .iter()
+        .find(|label| label.class.is_known() && label.class.as_str() != "DontCare")
+        .unwrap();
+
+    let expect = object::Label {
+        class: label.class.clone(),
+        truncation: None,
+        occlusion: None,
+        alpha: label.alpha,
+        bbox: label.bbox.clone(),
+        extents: label.extents.clone(),
+        location: label.location.clone(),
+        rotation_y: label.rotation_y,
+    };
+    assert_points_eq(&label.corners(), &expect.corners());
+    assert_points_eq(&[label.center()], &[expect.center()]);
+}
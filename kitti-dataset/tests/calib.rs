@@ -1,4 +1,4 @@
-use kitti_dataset::{object, odometry, tracking};
+use kitti_dataset::{object, odometry, raw, tracking};
 
 #[test]
 fn parse_calib() {
@@ -6,3 +6,54 @@ fn parse_calib() {
     let _ = tracking::Calibration::from_path("tests/tracking_calib.txt").unwrap();
     let _ = odometry::Calibration::from_path("tests/odometry_calib.txt").unwrap();
 }
+
+#[test]
+fn calib_round_trip() {
+    let calib1 = object::Calibration::from_path("tests/object_calib.txt").unwrap();
+    let calib2: object::Calibration = calib1.to_string().parse().unwrap();
+    assert_eq!(calib1, calib2);
+
+    let calib1 = tracking::Calibration::from_path("tests/tracking_calib.txt").unwrap();
+    let calib2: tracking::Calibration = calib1.to_string().parse().unwrap();
+    assert_eq!(calib1, calib2);
+
+    let calib1 = odometry::Calibration::from_path("tests/odometry_calib.txt").unwrap();
+    let calib2: odometry::Calibration = calib1.to_string().parse().unwrap();
+    assert_eq!(calib1, calib2);
+}
+
+#[test]
+fn parse_raw_calib() {
+    let cam_to_cam = raw::CamToCamCalibration::from_path("tests/raw_calib_cam_to_cam.txt").unwrap();
+    assert_eq!(cam_to_cam.calib_time, "09-Jan-2012 13:57:47");
+    assert_eq!(cam_to_cam.corner_dist, 9.95e-2);
+    assert_eq!(cam_to_cam.s_rect_02, [1242.0, 375.0]);
+    assert_eq!(
+        cam_to_cam.d_02,
+        [
+            -3.691481e-1,
+            1.968681e-1,
+            1.353473e-3,
+            5.677587e-4,
+            -6.770705e-2
+        ]
+    );
+    assert_eq!(cam_to_cam.p_rect_02.0[0][3], 4.485728e+1);
+
+    let velo_to_cam =
+        raw::VeloToCamCalibration::from_path("tests/raw_calib_velo_to_cam.txt").unwrap();
+    assert_eq!(velo_to_cam.calib_time, "15-Mar-2012 11:37:16");
+    assert_eq!(velo_to_cam.t, [-4.069766e-3, -7.631618e-2, -2.717806e-1]);
+    assert_eq!(velo_to_cam.delta_f, [0.0, 0.0]);
+
+    let imu_to_velo =
+        raw::ImuToVeloCalibration::from_path("tests/raw_calib_imu_to_velo.txt").unwrap();
+    assert_eq!(imu_to_velo.calib_time, "25-May-2012 16:47:16");
+    assert_eq!(imu_to_velo.r.0[2], [2.024406e-3, 1.482454e-2, 9.998881e-1]);
+
+    let text = cam_to_cam.to_string();
+    assert!(text.starts_with("calib_time: 09-Jan-2012 13:57:47\ncorner_dist: "));
+    assert_eq!(cam_to_cam, text.parse().unwrap());
+    assert_eq!(velo_to_cam, velo_to_cam.to_string().parse().unwrap());
+    assert_eq!(imu_to_velo, imu_to_velo.to_string().parse().unwrap());
+}
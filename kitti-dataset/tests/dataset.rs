@@ -0,0 +1,478 @@
+use kitti_dataset::{
+    dataset::{
+        object, odometry, raw, tracking, IndexMode, MismatchPolicy, ObjectDataset, OdometryDataset,
+        OpenOptions, RawDataset, TrackingDataset,
+    },
+    Error,
+};
+use std::{fs, path::Path};
+
+fn touch(dir: &Path, names: impl IntoIterator<Item = String>) {
+    fs::create_dir_all(dir).unwrap();
+    for name in names {
+        fs::write(dir.join(name), b"").unwrap();
+    }
+}
+
+#[test]
+fn object_dataset_mismatched_keys() {
+    let dir = std::env::temp_dir().join("kitti-dataset-object-mismatch");
+    let _ = fs::remove_dir_all(&dir);
+    touch(
+        &dir.join("velodyne"),
+        (0..4).map(|idx| format!("{idx:06}.bin")),
+    );
+    touch(
+        &dir.join("image_2"),
+        (0..5).map(|idx| format!("{idx:06}.png")),
+    );
+    touch(
+        &dir.join("label_2"),
+        (0..3).map(|idx| format!("{idx:06}.txt")),
+    );
+
+    let dataset = ObjectDataset::open(&dir).unwrap();
+    let keys: Vec<_> = dataset.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            ("image_2", object::DataKind::Image),
+            ("label_2", object::DataKind::Label),
+            ("velodyne", object::DataKind::Velodyne),
+        ]
+    );
+    let counts: Vec<_> = dataset.key_num_frames().collect();
+    assert_eq!(counts, [("image_2", 5), ("label_2", 3), ("velodyne", 4)]);
+    assert_eq!(dataset.num_frames(), 3);
+    assert_eq!(dataset.key("image_2").unwrap().num_frames(), 5);
+    assert!(dataset.key("image_2").unwrap().frame(3).is_none());
+
+    let dataset = ObjectDataset::open_with(&dir, MismatchPolicy::Union).unwrap();
+    assert_eq!(dataset.num_frames(), 5);
+    let frame = dataset.frame(3).unwrap();
+    let kinds: Vec<_> = frame.sample_iter().map(|sample| sample.kind()).collect();
+    assert_eq!(kinds, [object::DataKind::Image, object::DataKind::Velodyne]);
+    assert!(frame.key("label_2").is_none());
+    assert!(dataset.key("label_2").unwrap().frame(3).is_none());
+    assert!(dataset.key("image_2").unwrap().frame(4).is_some());
+
+    let err = ObjectDataset::open_with(&dir, MismatchPolicy::Error).unwrap_err();
+    let Error::FrameMismatch { keys } = err else {
+        panic!("unexpected error {err}");
+    };
+    let missing: Vec<_> = keys
+        .iter()
+        .map(|key| {
+            (
+                key.key.as_str(),
+                key.num_frames,
+                key.num_missing,
+                key.first_missing,
+            )
+        })
+        .collect();
+    assert_eq!(
+        missing,
+        [
+            ("image_2", 5, 0, None),
+            ("label_2", 3, 2, Some(3)),
+            ("velodyne", 4, 1, Some(4))
+        ]
+    );
+
+    // The frame IDs differ while the counts are equal, which only a
+    // scan can tell.
+    fs::remove_file(dir.join("image_2/000004.png")).unwrap();
+    fs::remove_file(dir.join("image_2/000003.png")).unwrap();
+    fs::remove_file(dir.join("velodyne/000003.bin")).unwrap();
+    fs::remove_file(dir.join("label_2/000000.txt")).unwrap();
+    touch(&dir.join("label_2"), ["000003.txt".to_string()]);
+    let options = OpenOptions::new()
+        .with_mismatch_policy(MismatchPolicy::Error)
+        .with_index_mode(IndexMode::Scan);
+    let err = ObjectDataset::open_with_options(&dir, options).unwrap_err();
+    let Error::FrameMismatch { keys } = &err else {
+        panic!("unexpected error {err}");
+    };
+    let first_missing: Vec<_> = keys.iter().map(|key| key.first_missing).collect();
+    assert_eq!(first_missing, [Some(3), Some(0), Some(3)]);
+    assert_eq!(
+        err.to_string(),
+        "sub-directories contain mismatched frames: \
+         image_2 (3 frames, 1 missing, first missing ID 3), \
+         label_2 (3 frames, 1 missing, first missing ID 0), \
+         velodyne (3 frames, 1 missing, first missing ID 3)"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn tracking_dataset_mismatched_keys() {
+    let dir = std::env::temp_dir().join("kitti-dataset-tracking-mismatch");
+    let _ = fs::remove_dir_all(&dir);
+    touch(
+        &dir.join("label_02"),
+        (0..2).map(|idx| format!("{idx:04}.txt")),
+    );
+    for seq in 0..3 {
+        touch(
+            &dir.join("image_02").join(format!("{seq:04}")),
+            (0..seq + 1).map(|idx| format!("{idx:06}.png")),
+        );
+    }
+
+    let dataset = TrackingDataset::open(&dir).unwrap();
+    let keys: Vec<_> = dataset.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            ("image_02", tracking::DataKind::ImageSeq),
+            ("label_02", tracking::DataKind::Label),
+        ]
+    );
+    assert_eq!(dataset.num_frames(), 2);
+    assert_eq!(dataset.frame(1).unwrap().seq_len(), Some(2));
+
+    let dataset = TrackingDataset::open_with(&dir, MismatchPolicy::Union).unwrap();
+    assert_eq!(dataset.num_frames(), 3);
+    let frame = dataset.frame(2).unwrap();
+    assert_eq!(frame.sample_iter().count(), 1);
+    assert_eq!(frame.seq_len(), Some(3));
+
+    assert!(TrackingDataset::open_with(&dir, MismatchPolicy::Error).is_err());
+
+    // Sequence frames after a gap are found by scanning.
+    fs::remove_file(dir.join("image_02/0002/000001.png")).unwrap();
+    let dataset = TrackingDataset::open_with(&dir, MismatchPolicy::Union).unwrap();
+    assert_eq!(dataset.frame(2).unwrap().seq_len(), Some(1));
+
+    let options = OpenOptions::new()
+        .with_index_mode(IndexMode::Scan)
+        .with_mismatch_policy(MismatchPolicy::Union);
+    let dataset = TrackingDataset::open_with_options(&dir, options).unwrap();
+    let frame = dataset.frame(2).unwrap();
+    assert_eq!(frame.seq_len(), Some(2));
+    let tracking::SampleData::ImageSeq(seq) = frame.key("image_02").unwrap().data().unwrap() else {
+        panic!("expect an image sequence");
+    };
+    assert_eq!(seq.seq_len(), 2);
+    assert_eq!(seq.index().ids().collect::<Vec<_>>(), [0, 2]);
+    assert!(seq.get(1).unwrap().is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn object_dataset_scan_sparse_ids() {
+    let dir = std::env::temp_dir().join("kitti-dataset-object-scan");
+    let _ = fs::remove_dir_all(&dir);
+    let ids = [0, 2, 5, 7];
+    touch(&dir.join("image_2"), ids.map(|id| format!("{id:06}.png")));
+    touch(&dir.join("label_2"), ids.map(|id| format!("{id:06}.txt")));
+    touch(
+        &dir.join("image_2"),
+        ["readme.md".to_string(), "12.png".to_string()],
+    );
+
+    // Probing stops at the first missing frame.
+    let dataset = ObjectDataset::open(&dir).unwrap();
+    assert_eq!(dataset.frame_ids(), [0]);
+
+    let options = OpenOptions::new().with_index_mode(IndexMode::Scan);
+    let dataset = ObjectDataset::open_with_options(&dir, options).unwrap();
+    assert_eq!(dataset.frame_ids(), ids);
+    assert_eq!(dataset.num_frames(), 4);
+    assert_eq!(dataset.frame(2).unwrap().id(), 5);
+    assert!(dataset.frame_by_id(3).is_none());
+
+    let frame = dataset.frame_by_id(7).unwrap();
+    let paths: Vec<_> = frame
+        .sample_iter()
+        .map(|sample| sample.path().to_owned())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            dir.join("image_2").join("000007.png"),
+            dir.join("label_2").join("000007.txt")
+        ]
+    );
+
+    let key = dataset.key("image_2").unwrap();
+    assert_eq!(key.frame(1).unwrap().path(), dir.join("image_2/000002.png"));
+    assert!(key.frame_by_id(1).is_none());
+
+    let index = key.index();
+    assert_eq!(index.ids().collect::<Vec<_>>(), ids);
+    assert_eq!(index.gaps(), [1..2, 3..5, 6..7]);
+    assert_eq!(
+        index.stray_files(),
+        [
+            dir.join("image_2").join("12.png"),
+            dir.join("image_2").join("readme.md")
+        ]
+    );
+    assert!(dataset
+        .key("label_2")
+        .unwrap()
+        .index()
+        .stray_files()
+        .is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn object_dataset_scan_dangling_symlink() {
+    let dir = std::env::temp_dir().join("kitti-dataset-object-symlink");
+    let _ = fs::remove_dir_all(&dir);
+    touch(
+        &dir.join("image_2"),
+        (0..2).map(|idx| format!("{idx:06}.png")),
+    );
+    std::os::unix::fs::symlink(dir.join("missing.png"), dir.join("image_2/000002.png")).unwrap();
+    std::os::unix::fs::symlink(
+        dir.join("image_2/000000.png"),
+        dir.join("image_2/000003.png"),
+    )
+    .unwrap();
+
+    let options = OpenOptions::new().with_index_mode(IndexMode::Scan);
+    let dataset = ObjectDataset::open_with_options(&dir, options).unwrap();
+    assert_eq!(dataset.frame_ids(), [0, 1, 3]);
+    let index = dataset.key("image_2").unwrap().index();
+    assert_eq!(index.stray_files(), [dir.join("image_2/000002.png")]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn raw_dataset_drives_and_frames() {
+    let dir = std::env::temp_dir().join("kitti-dataset-raw");
+    let _ = fs::remove_dir_all(&dir);
+    let date_dir = dir.join("2011_09_26");
+    let drive_dir = date_dir.join("2011_09_26_drive_0001_sync");
+    touch(
+        &drive_dir.join("image_00").join("data"),
+        (0..3).map(|idx| format!("{idx:010}.png")),
+    );
+    touch(
+        &drive_dir.join("velodyne_points").join("data"),
+        (0..3).map(|idx| format!("{idx:010}.bin")),
+    );
+    let oxts_dir = drive_dir.join("oxts").join("data");
+    fs::create_dir_all(&oxts_dir).unwrap();
+    for idx in 0..3 {
+        fs::copy("tests/oxts.txt", oxts_dir.join(format!("{idx:010}.txt"))).unwrap();
+    }
+    fs::copy(
+        "tests/timestamps.txt",
+        drive_dir.join("image_00").join("timestamps.txt"),
+    )
+    .unwrap();
+    for name in ["cam_to_cam", "imu_to_velo", "velo_to_cam"] {
+        fs::copy(
+            format!("tests/raw_calib_{name}.txt"),
+            date_dir.join(format!("calib_{name}.txt")),
+        )
+        .unwrap();
+    }
+    touch(
+        &dir.join("2011_09_28").join("2011_09_28_drive_0002_sync"),
+        [],
+    );
+    touch(&dir.join("devkit"), []);
+    touch(&date_dir.join("other_dir"), []);
+
+    let dataset = RawDataset::open(&dir).unwrap();
+    assert_eq!(dataset.num_drives(), 2);
+    assert_eq!(
+        dataset.dates().collect::<Vec<_>>(),
+        ["2011_09_26", "2011_09_28"]
+    );
+
+    let empty = dataset.drive("2011_09_28_drive_0002_sync").unwrap();
+    assert_eq!(empty.num_frames(), 0);
+    assert!(empty.frame(0).is_none());
+
+    let drive = dataset.drive("2011_09_26_drive_0001_sync").unwrap();
+    assert_eq!(drive.date(), "2011_09_26");
+    assert_eq!(drive.dir(), drive_dir);
+    assert_eq!(drive.num_frames(), 3);
+    assert_eq!(drive.frame_iter().count(), 3);
+    assert!(drive.frame(3).is_none());
+    assert!(drive.calibration().is_ok());
+
+    let keys: Vec<_> = drive.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            ("image_00", raw::DataKind::Image),
+            ("oxts", raw::DataKind::Oxts),
+            ("velodyne_points", raw::DataKind::Velodyne),
+        ]
+    );
+    assert!(drive.timestamps("image_00").is_some());
+    assert!(drive.timestamps("oxts").is_none());
+
+    let frame = drive.frame(2).unwrap();
+    assert_eq!(
+        frame.timestamp("image_00"),
+        Some(&drive.timestamps("image_00").unwrap()[2])
+    );
+    assert_eq!(frame.sample_iter().count(), 3);
+    let sample = frame.key("oxts").unwrap();
+    assert_eq!(sample.path(), oxts_dir.join("0000000002.txt"));
+    assert!(matches!(sample.data().unwrap(), raw::SampleData::Oxts(_)));
+    let sample = frame.key("velodyne_points").unwrap();
+    assert!(sample
+        .point_cloud_view()
+        .unwrap()
+        .unwrap()
+        .points()
+        .is_empty());
+
+    let key = drive.key("image_00").unwrap();
+    assert_eq!(
+        key.frame(1).unwrap().path(),
+        drive_dir.join("image_00/data/0000000001.png")
+    );
+    assert!(key.frame(3).is_none());
+    assert!(drive.key("image_01").is_none());
+
+    // Frames after a gap are found by scanning.
+    touch(
+        &drive_dir.join("image_00").join("data"),
+        ["0000000005.png".to_string(), "readme.txt".to_string()],
+    );
+    touch(
+        &drive_dir.join("velodyne_points").join("data"),
+        ["0000000005.bin".to_string()],
+    );
+    let options = OpenOptions::new()
+        .with_index_mode(IndexMode::Scan)
+        .with_mismatch_policy(MismatchPolicy::Union);
+    let dataset = RawDataset::open_with_options(&dir, options).unwrap();
+    let drive = dataset.drive("2011_09_26_drive_0001_sync").unwrap();
+    assert_eq!(drive.frame_ids(), [0, 1, 2, 5]);
+    let frame = drive.frame(3).unwrap();
+    assert_eq!(frame.id(), 5);
+    assert_eq!(
+        frame.timestamp("image_00"),
+        Some(&drive.timestamps("image_00").unwrap()[5])
+    );
+    assert_eq!(frame.sample_iter().count(), 2);
+    assert!(frame.key("oxts").is_none());
+    let key = drive.key("image_00").unwrap();
+    assert_eq!(key.num_frames(), 4);
+    assert_eq!(
+        key.index().stray_files(),
+        [drive_dir.join("image_00/data/readme.txt")]
+    );
+    assert_eq!(
+        key.frame_by_id(5).unwrap().path(),
+        drive_dir.join("image_00/data/0000000005.png")
+    );
+
+    let dataset = RawDataset::open_with(&dir, MismatchPolicy::Error).unwrap();
+    assert_eq!(dataset.drive_iter().next().unwrap().num_frames(), 3);
+    let options = options.with_mismatch_policy(MismatchPolicy::Error);
+    assert!(matches!(
+        RawDataset::open_with_options(&dir, options),
+        Err(Error::FrameMismatch { .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn odometry_dataset_sequences() {
+    let dir = std::env::temp_dir().join("kitti-dataset-odometry");
+    let _ = fs::remove_dir_all(&dir);
+    for seq in ["00", "11"] {
+        let seq_dir = dir.join("sequences").join(seq);
+        touch(
+            &seq_dir.join("image_0"),
+            (0..4).map(|idx| format!("{idx:06}.png")),
+        );
+        touch(
+            &seq_dir.join("velodyne"),
+            (0..4).map(|idx| format!("{idx:06}.bin")),
+        );
+        fs::copy("tests/times.txt", seq_dir.join("times.txt")).unwrap();
+        fs::copy("tests/odometry_calib.txt", seq_dir.join("calib.txt")).unwrap();
+    }
+    touch(&dir.join("sequences").join("devkit"), []);
+    fs::create_dir_all(dir.join("poses")).unwrap();
+    fs::copy("tests/pose.txt", dir.join("poses").join("00.txt")).unwrap();
+
+    let dataset = OdometryDataset::open(&dir).unwrap();
+    assert_eq!(dataset.num_sequences(), 2);
+    let names: Vec<_> = dataset
+        .sequence_iter()
+        .map(|seq| seq.name().to_string())
+        .collect();
+    assert_eq!(names, ["00", "11"]);
+
+    let seq = dataset.sequence("00").unwrap();
+    assert_eq!(seq.dir(), dir.join("sequences").join("00"));
+    assert_eq!(seq.num_frames(), 4);
+    assert!(seq.frame(4).is_none());
+    assert!(seq.calibration().is_ok());
+    let keys: Vec<_> = seq.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            ("image_0", odometry::DataKind::Image),
+            ("velodyne", odometry::DataKind::Velodyne),
+        ]
+    );
+
+    let frame = seq.frame(3).unwrap();
+    assert_eq!(frame.timestamp(), Some(&seq.times()[3]));
+    assert_eq!(frame.pose(), Some(&seq.poses().unwrap()[3]));
+    assert_eq!(
+        frame.key("velodyne").unwrap().path(),
+        dir.join("sequences/00/velodyne/000003.bin")
+    );
+    assert_eq!(frame.sample_iter().count(), 2);
+    assert!(seq.key("image_0").unwrap().frame(4).is_none());
+
+    // Test sequences have no ground truth poses.
+    let seq = dataset.sequence("11").unwrap();
+    assert!(seq.poses().is_none());
+    assert!(seq.frame(0).unwrap().pose().is_none());
+
+    // Frames after a gap are found by scanning, and times and poses
+    // are looked up by frame ID.
+    fs::remove_file(dir.join("sequences/00/image_0/000001.png")).unwrap();
+    let dataset = OdometryDataset::open(&dir).unwrap();
+    assert_eq!(dataset.sequence("00").unwrap().frame_ids(), [0]);
+    let options = OpenOptions::new().with_index_mode(IndexMode::Scan);
+    let dataset = OdometryDataset::open_with_options(&dir, options).unwrap();
+    let seq = dataset.sequence("00").unwrap();
+    assert_eq!(seq.frame_ids(), [0, 2, 3]);
+    let frame = seq.frame(1).unwrap();
+    assert_eq!(frame.id(), 2);
+    assert_eq!(frame.timestamp(), Some(&seq.times()[2]));
+    assert_eq!(frame.pose(), Some(&seq.poses().unwrap()[2]));
+    assert!(seq.frame_by_id(1).is_none());
+    let key = seq.key("image_0").unwrap();
+    assert_eq!(key.index().gaps(), vec![1..2]);
+    assert_eq!(key.num_frames(), 3);
+
+    let dataset = OdometryDataset::open_with(&dir, MismatchPolicy::Union).unwrap();
+    let seq = dataset.sequence("00").unwrap();
+    assert_eq!(seq.num_frames(), 4);
+    let frame = seq.frame_by_id(1).unwrap();
+    assert!(frame.key("image_0").is_none());
+    assert_eq!(frame.sample_iter().count(), 1);
+
+    // The times file is mandatory.
+    fs::remove_file(dir.join("sequences/11/times.txt")).unwrap();
+    assert!(matches!(OdometryDataset::open(&dir), Err(Error::Io(_))));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
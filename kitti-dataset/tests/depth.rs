@@ -0,0 +1,245 @@
+use image::{DynamicImage, ImageBuffer, Luma, RgbImage};
+use kitti_dataset::{
+    dataset::DepthDataset,
+    depth::{DepthMap, Intrinsics},
+    Error,
+};
+use std::fs;
+
+#[test]
+fn depth_encoding() {
+    let image: ImageBuffer<Luma<u16>, Vec<u16>> =
+        ImageBuffer::from_raw(2, 2, vec![0, 256, 5120, 65535]).unwrap();
+    let depth = DepthMap::from_image(&DynamicImage::ImageLuma16(image.clone())).unwrap();
+
+    assert_eq!(depth.num_valid(), 3);
+    assert_eq!(depth.valid_mask(), [false, true, true, true]);
+    assert_eq!(depth.get(0, 0), None);
+    assert_eq!(depth.get(1, 0), Some(1.0));
+    assert_eq!(depth.get(0, 1), Some(20.0));
+    assert_eq!(depth.to_image(), image);
+
+    let path = std::env::temp_dir().join("kitti-dataset-depth.png");
+    depth.write_to_path(&path).unwrap();
+    let loaded = DepthMap::from_path(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, depth);
+
+    let mut depth = DepthMap::new(2, 1);
+    depth.set(0, 0, Some(-5.0));
+    depth.set(1, 0, Some(2.5));
+    assert_eq!(depth.get(0, 0), None);
+    assert_eq!(depth.to_image().into_raw(), [0, 640]);
+
+    let rgb = DynamicImage::ImageRgb8(RgbImage::new(2, 2));
+    let err = DepthMap::from_image(&rgb).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid image format: expect a 16-bit grayscale depth image, but get Rgb8"
+    );
+}
+
+#[test]
+fn intrinsics_round_trip() {
+    let text = "7.215377e+02 0.000000e+00 5.966593e+02 0.000000e+00 7.215377e+02 1.498540e+02 0.000000e+00 0.000000e+00 1.000000e+00\n";
+    let intrinsics: Intrinsics = text.parse().unwrap();
+    assert_eq!(intrinsics.write_to_string().unwrap(), text);
+
+    assert!(matches!(
+        "1 2 3".parse::<Intrinsics>(),
+        Err(Error::InvalidIntrinsics(_))
+    ));
+}
+
+#[test]
+fn open_depth_dataset() {
+    let dir = std::env::temp_dir().join("kitti-dataset-depth-selection");
+    let _ = fs::remove_dir_all(&dir);
+    for key in ["groundtruth_depth", "velodyne_raw", "image", "intrinsics"] {
+        fs::create_dir_all(dir.join(key)).unwrap();
+    }
+
+    let intrinsics = "7.215377e+02 0.000000e+00 5.966593e+02 0.000000e+00 7.215377e+02 1.498540e+02 0.000000e+00 0.000000e+00 1.000000e+00\n";
+    for frame in [5, 20] {
+        let name = |key: &str, ext: &str| {
+            format!("2011_09_26_drive_0002_sync_{key}_{frame:010}_image_02.{ext}")
+        };
+
+        let mut depth = DepthMap::new(4, 3);
+        depth.set(1, 1, Some(frame as f32));
+        depth
+            .write_to_path(
+                dir.join("groundtruth_depth")
+                    .join(name("groundtruth_depth", "png")),
+            )
+            .unwrap();
+        DepthMap::new(4, 3)
+            .write_to_path(dir.join("velodyne_raw").join(name("velodyne_raw", "png")))
+            .unwrap();
+        RgbImage::new(4, 3)
+            .save(dir.join("image").join(name("image", "png")))
+            .unwrap();
+        fs::write(
+            dir.join("intrinsics").join(name("image", "txt")),
+            intrinsics,
+        )
+        .unwrap();
+    }
+
+    let dataset = DepthDataset::open(&dir).unwrap();
+    assert_eq!(dataset.num_frames(), 2);
+
+    let frame = dataset.frame(1).unwrap();
+    assert_eq!(
+        frame.name(),
+        "2011_09_26_drive_0002_sync_groundtruth_depth_0000000020_image_02.png"
+    );
+    assert_eq!(
+        frame.image_path().unwrap(),
+        dir.join("image")
+            .join("2011_09_26_drive_0002_sync_image_0000000020_image_02.png")
+    );
+    assert_eq!(
+        frame.velodyne_raw_path().unwrap(),
+        dir.join("velodyne_raw")
+            .join("2011_09_26_drive_0002_sync_velodyne_raw_0000000020_image_02.png")
+    );
+    assert_eq!(
+        frame.intrinsics_path().unwrap(),
+        dir.join("intrinsics")
+            .join("2011_09_26_drive_0002_sync_image_0000000020_image_02.txt")
+    );
+    assert_eq!(frame.groundtruth().unwrap().unwrap().get(1, 1), Some(20.0));
+    assert_eq!(frame.velodyne_raw().unwrap().unwrap().num_valid(), 0);
+    assert_eq!(
+        frame
+            .intrinsics()
+            .unwrap()
+            .unwrap()
+            .write_to_string()
+            .unwrap(),
+        intrinsics
+    );
+
+    // A missing paired file is reported.
+    let missing = dir
+        .join("velodyne_raw")
+        .join("2011_09_26_drive_0002_sync_velodyne_raw_0000000005_image_02.png");
+    fs::remove_file(&missing).unwrap();
+    assert!(matches!(
+        DepthDataset::open(&dir),
+        Err(Error::MissingPairedFile(path)) if path == missing
+    ));
+
+    // Without depth maps, samples are listed from the images.
+    fs::remove_dir_all(dir.join("groundtruth_depth")).unwrap();
+    fs::remove_dir_all(dir.join("velodyne_raw")).unwrap();
+    let dataset = DepthDataset::open(&dir).unwrap();
+    assert_eq!(dataset.num_frames(), 2);
+    let frame = dataset.frame(0).unwrap();
+    assert!(frame.groundtruth_path().is_none());
+    assert!(frame.intrinsics_path().is_some());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn open_depth_dataset_proj_depth() {
+    let dir = std::env::temp_dir().join("kitti-dataset-depth-train");
+    let _ = fs::remove_dir_all(&dir);
+    let train_dir = dir.join("train");
+    let raw_dir = dir.join("raw");
+    let drive = "2011_09_26_drive_0001_sync";
+    let proj_dir = train_dir.join(drive).join("proj_depth");
+
+    for camera in ["image_02", "image_03"] {
+        for frame in [5, 6] {
+            let file_name = format!("{frame:010}.png");
+            let mut depth = DepthMap::new(4, 3);
+            depth.set(0, 0, Some(frame as f32));
+            let gt_dir = proj_dir.join("groundtruth").join(camera);
+            fs::create_dir_all(&gt_dir).unwrap();
+            depth.write_to_path(gt_dir.join(&file_name)).unwrap();
+
+            let velodyne_dir = proj_dir.join("velodyne_raw").join(camera);
+            fs::create_dir_all(&velodyne_dir).unwrap();
+            DepthMap::new(4, 3)
+                .write_to_path(velodyne_dir.join(&file_name))
+                .unwrap();
+        }
+
+        // The raw data has frames without ground truth.
+        let image_dir = raw_dir
+            .join("2011_09_26")
+            .join(drive)
+            .join(camera)
+            .join("data");
+        fs::create_dir_all(&image_dir).unwrap();
+        for frame in 0..8 {
+            RgbImage::new(4, 3)
+                .save(image_dir.join(format!("{frame:010}.png")))
+                .unwrap();
+        }
+    }
+    fs::create_dir_all(train_dir.join("devkit")).unwrap();
+
+    let dataset = DepthDataset::open(&train_dir).unwrap();
+    assert_eq!(dataset.num_frames(), 4);
+    let frame = dataset.frame(1).unwrap();
+    assert!(frame.image_path().is_none());
+    assert!(frame.intrinsics_path().is_none());
+
+    let dataset = DepthDataset::open_with_raw_dir(&train_dir, &raw_dir).unwrap();
+    let names: Vec<_> = dataset.frame_iter().map(|frame| frame.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "2011_09_26_drive_0001_sync_groundtruth_depth_0000000005_image_02.png",
+            "2011_09_26_drive_0001_sync_groundtruth_depth_0000000006_image_02.png",
+            "2011_09_26_drive_0001_sync_groundtruth_depth_0000000005_image_03.png",
+            "2011_09_26_drive_0001_sync_groundtruth_depth_0000000006_image_03.png",
+        ]
+    );
+
+    let frame = dataset.frame(3).unwrap();
+    assert_eq!(
+        frame.groundtruth_path().unwrap(),
+        proj_dir.join("groundtruth/image_03/0000000006.png")
+    );
+    assert_eq!(
+        frame.velodyne_raw_path().unwrap(),
+        proj_dir.join("velodyne_raw/image_03/0000000006.png")
+    );
+    assert_eq!(
+        frame.image_path().unwrap(),
+        raw_dir
+            .join("2011_09_26")
+            .join(drive)
+            .join("image_03/data/0000000006.png")
+    );
+    assert_eq!(frame.groundtruth().unwrap().unwrap().get(0, 0), Some(6.0));
+    assert!(frame.image().unwrap().is_some());
+
+    // A missing paired file is reported.
+    let missing = proj_dir.join("velodyne_raw/image_02/0000000006.png");
+    fs::remove_file(&missing).unwrap();
+    assert!(matches!(
+        DepthDataset::open(&train_dir),
+        Err(Error::MissingPairedFile(path)) if path == missing
+    ));
+
+    // Without ground truth, samples are listed from the sparse depth
+    // maps.
+    fs::remove_dir_all(proj_dir.join("groundtruth")).unwrap();
+    let dataset = DepthDataset::open_with_raw_dir(&train_dir, &raw_dir).unwrap();
+    assert_eq!(dataset.num_frames(), 3);
+    let frame = dataset.frame(0).unwrap();
+    assert_eq!(
+        frame.name(),
+        "2011_09_26_drive_0001_sync_velodyne_raw_0000000005_image_02.png"
+    );
+    assert!(frame.groundtruth_path().is_none());
+    assert!(frame.image_path().is_some());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
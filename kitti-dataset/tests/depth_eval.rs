@@ -0,0 +1,109 @@
+use kitti_dataset::{
+    depth::{
+        eval::{evaluate, evaluate_image},
+        DepthMap,
+    },
+    Error,
+};
+
+fn depth_map(width: u32, height: u32, values: &[Option<f32>]) -> DepthMap {
+    let mut depth = DepthMap::new(width, height);
+    for (idx, &value) in values.iter().enumerate() {
+        let idx = idx as u32;
+        depth.set(idx % width, idx / width, value);
+    }
+    depth
+}
+
+fn assert_close(value: f64, expect: f64) {
+    assert!((value - expect).abs() < 1e-6, "{value} != {expect}");
+}
+
+#[test]
+fn interpolate_background() {
+    let mut depth = depth_map(
+        6,
+        2,
+        &[
+            None,
+            Some(3.0),
+            None,
+            None,
+            Some(5.0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ],
+    );
+    depth.interpolate_background();
+
+    assert_eq!(depth.num_valid(), 12);
+    assert_eq!(
+        depth.values(),
+        [3.0, 3.0, 5.0, 5.0, 5.0, 5.0, 3.0, 3.0, 5.0, 5.0, 5.0, 5.0]
+    );
+
+    // An empty map stays empty.
+    let mut depth = DepthMap::new(3, 2);
+    depth.interpolate_background();
+    assert_eq!(depth.num_valid(), 0);
+}
+
+#[test]
+fn evaluate_depth_errors() {
+    let gt = depth_map(3, 1, &[Some(2.0), Some(4.0), None]);
+    let pred = depth_map(3, 1, &[Some(1.0), Some(4.0), Some(100.0)]);
+
+    let metrics = evaluate_image(&gt, &pred).unwrap().unwrap();
+    assert_close(metrics.mae, 500.0);
+    assert_close(metrics.rmse, 0.5f64.sqrt() * 1000.0);
+    assert_close(metrics.imae, 250.0);
+    assert_close(metrics.irmse, 0.125f64.sqrt() * 1000.0);
+    assert_close(metrics.abs_rel, 25.0);
+    assert_close(metrics.sq_rel, 12.5);
+    assert_close(metrics.silog, 2f64.ln() / 2.0 * 100.0);
+
+    // Invalid predictions are filled before evaluation.
+    let sparse = depth_map(3, 1, &[None, Some(4.0), None]);
+    let metrics = evaluate_image(&gt, &sparse).unwrap().unwrap();
+    assert_close(metrics.mae, 1000.0);
+
+    // Errors are averaged over images rather than over pixels.
+    let result = evaluate([(&gt, &pred), (&gt, &gt)]).unwrap();
+    let metrics = result.metrics().unwrap();
+    assert_close(metrics.mae, 250.0);
+    assert_close(metrics.rmse, 0.5f64.sqrt() * 500.0);
+
+    let empty = DepthMap::new(3, 1);
+    let result = evaluate([(&empty, &pred)]).unwrap();
+    assert_eq!(result.images, [None]);
+    assert!(result.metrics().is_none());
+
+    // A prediction without valid pixels cannot be filled.
+    assert!(matches!(
+        evaluate_image(&gt, &DepthMap::new(3, 1)),
+        Err(Error::EmptyPrediction)
+    ));
+    assert!(matches!(
+        evaluate([(&gt, &pred), (&gt, &DepthMap::new(3, 1))]),
+        Err(Error::EmptyPrediction)
+    ));
+
+    // Zero predictions are skipped.
+    let zero = depth_map(3, 1, &[Some(0.0), Some(4.0), Some(100.0)]);
+    let metrics = evaluate_image(&gt, &zero).unwrap().unwrap();
+    assert_close(metrics.mae, 0.0);
+    assert!(metrics.irmse.is_finite() && metrics.silog.is_finite());
+
+    assert!(matches!(
+        evaluate_image(&gt, &DepthMap::new(2, 1)),
+        Err(Error::DepthSizeMismatch {
+            expect: (3, 1),
+            found: (2, 1)
+        })
+    ));
+}
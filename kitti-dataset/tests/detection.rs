@@ -0,0 +1,67 @@
+use kitti_dataset::{
+    object::{Class, Detection, Label},
+    Error,
+};
+use std::fs;
+
+const RESULTS: &str = "\
+Car -1 -1 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59 0.93
+Pedestrian -1 -1 0.21 423.17 173.67 433.17 224.03 1.60 0.38 0.30 -5.87 1.63 23.11 -0.03 0.25
+";
+
+#[test]
+fn parse_detection() {
+    let detections = Detection::vec_from_str(RESULTS).unwrap();
+    assert_eq!(detections.len(), 2);
+    assert_eq!(detections[0].label.class, Class::Car);
+    assert_eq!(detections[0].score, 0.93);
+    assert_eq!(detections[1].label.class, Class::Pedestrian);
+    assert_eq!(detections[1].score, 0.25);
+
+    let text = Detection::write_to_string(&detections).unwrap();
+    let detections2 = Detection::vec_from_str(&text).unwrap();
+    assert_eq!(detections, detections2);
+
+    // Ground truth without scores
+    let labels = Label::vec_from_path("tests/object_label.txt").unwrap();
+    let detections = Detection::vec_from_path("tests/object_label.txt").unwrap();
+    assert_eq!(detections.len(), labels.len());
+    for (detection, label) in detections.iter().zip(&labels) {
+        assert_eq!(&detection.label, label);
+        assert_eq!(detection.score, 1.0);
+    }
+}
+
+#[test]
+fn parse_invalid_detection() {
+    let text =
+        "Car -1 -1 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59 high\n";
+    assert!(matches!(
+        Detection::vec_from_str(text),
+        Err(Error::InvalidScore(score)) if score == "high"
+    ));
+
+    let text = "Car -1 -1 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59 0.5 0.5\n";
+    assert!(matches!(
+        Detection::vec_from_str(text),
+        Err(Error::InvalidDetectionLength { line: 1, found: 17 })
+    ));
+}
+
+#[test]
+fn read_detection_dir() {
+    let dir = std::env::temp_dir().join("kitti-dataset-detection-dir");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(dir.join("000000.txt"), RESULTS).unwrap();
+    fs::write(dir.join("000007.txt"), "").unwrap();
+    fs::write(dir.join("README.md"), "results").unwrap();
+
+    let map = Detection::map_from_dir(&dir).unwrap();
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![0, 7]);
+    assert_eq!(map[&0].len(), 2);
+    assert!(map[&7].is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
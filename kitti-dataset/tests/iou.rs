@@ -0,0 +1,128 @@
+use kitti_dataset::object::{
+    iou::{bbox_iou_matrix, bev_iou_matrix, box3d_iou_matrix},
+    BoundingBox, Class, Extents, Label, Location,
+};
+use measurements::{Angle, Length};
+use std::f64::consts::FRAC_PI_4;
+
+const EPSILON: f64 = 1e-9;
+
+fn cube(x: f64, y: f64, z: f64, rotation_y: f64) -> Label {
+    Label {
+        class: Class::Car,
+        truncation: None,
+        occlusion: None,
+        alpha: Angle::from_radians(0.0),
+        bbox: BoundingBox {
+            xmin: 0.0,
+            ymin: 0.0,
+            xmax: 1.0,
+            ymax: 1.0,
+        },
+        extents: Extents {
+            height: Length::from_meters(2.0),
+            width: Length::from_meters(2.0),
+            length: Length::from_meters(2.0),
+        },
+        location: Location {
+            x: Length::from_meters(x),
+            y: Length::from_meters(y),
+            z: Length::from_meters(z),
+        },
+        rotation_y: Angle::from_radians(rotation_y),
+    }
+}
+
+#[test]
+fn bbox_iou() {
+    let lhs = BoundingBox {
+        xmin: 0.0,
+        ymin: 0.0,
+        xmax: 2.0,
+        ymax: 2.0,
+    };
+    let rhs = BoundingBox {
+        xmin: 1.0,
+        ymin: 1.0,
+        xmax: 3.0,
+        ymax: 3.0,
+    };
+    let far = BoundingBox {
+        xmin: 5.0,
+        ymin: 5.0,
+        xmax: 6.0,
+        ymax: 6.0,
+    };
+
+    assert!((lhs.iou(&rhs) - 1.0 / 7.0).abs() < EPSILON);
+    assert!((lhs.ioa(&rhs) - 0.25).abs() < EPSILON);
+    assert_eq!(lhs.iou(&far), 0.0);
+    assert_eq!(lhs.area(), 4.0);
+
+    let matrix = bbox_iou_matrix(&[&lhs, &rhs], &[&lhs, &rhs, &far]);
+    assert_eq!(matrix.len(), 2);
+    assert_eq!(matrix[0].len(), 3);
+    assert!((matrix[1][1] - 1.0).abs() < EPSILON);
+    assert!((matrix[1][0] - 1.0 / 7.0).abs() < EPSILON);
+}
+
+#[test]
+fn bev_iou() {
+    let base = cube(0.0, 0.0, 0.0, 0.0);
+
+    // Shifted by half of the length
+    let shifted = cube(1.0, 0.0, 0.0, 0.0);
+    assert!((base.bev_iou(&shifted) - 1.0 / 3.0).abs() < EPSILON);
+
+    // Rotated by 45 degrees, the intersection is a regular octagon.
+    let rotated = cube(0.0, 0.0, 0.0, FRAC_PI_4);
+    let octagon = 8.0 * (2f64.sqrt() - 1.0);
+    let expect = octagon / (8.0 - octagon);
+    assert!((base.bev_iou(&rotated) - expect).abs() < EPSILON);
+
+    // Rotating by 90 degrees gives the same square.
+    let flipped = cube(0.0, 0.0, 0.0, -std::f64::consts::FRAC_PI_2);
+    assert!((base.bev_iou(&flipped) - 1.0).abs() < EPSILON);
+
+    // The vertical offset is ignored in bird's eye view.
+    let lifted = cube(0.0, -1.0, 0.0, 0.0);
+    assert!((base.bev_iou(&lifted) - 1.0).abs() < EPSILON);
+
+    let far = cube(10.0, 0.0, 0.0, 0.3);
+    assert_eq!(base.bev_iou(&far), 0.0);
+}
+
+#[test]
+fn box3d_iou() {
+    let base = cube(0.0, 0.0, 0.0, 0.0);
+    assert!((base.box3d_iou(&base) - 1.0).abs() < EPSILON);
+
+    let lifted = cube(0.0, -1.0, 0.0, 0.0);
+    assert!((base.box3d_iou(&lifted) - 1.0 / 3.0).abs() < EPSILON);
+
+    let stacked = cube(0.0, -2.0, 0.0, 0.0);
+    assert_eq!(base.box3d_iou(&stacked), 0.0);
+
+    let shifted_lifted = cube(1.0, -1.0, 0.0, 0.0);
+    let expect = 2.0 / (16.0 - 2.0);
+    assert!((base.box3d_iou(&shifted_lifted) - expect).abs() < EPSILON);
+}
+
+#[test]
+fn label_iou_matrix() {
+    let labels = Label::vec_from_path("tests/object_label.txt").unwrap();
+    let bev = bev_iou_matrix(&labels, &labels);
+    let box3d = box3d_iou_matrix(&labels, &labels);
+
+    for (idx, (bev_row, box3d_row)) in bev.iter().zip(&box3d).enumerate() {
+        for (jdx, (bev, box3d)) in bev_row.iter().zip(box3d_row).enumerate() {
+            if idx == jdx {
+                assert!((bev - 1.0).abs() < EPSILON);
+                assert!((box3d - 1.0).abs() < EPSILON);
+            } else {
+                assert_eq!(*bev, 0.0);
+                assert_eq!(*box3d, 0.0);
+            }
+        }
+    }
+}
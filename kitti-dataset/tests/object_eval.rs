@@ -0,0 +1,119 @@
+use kitti_dataset::object::{
+    eval::{evaluate, Difficulty, Metric},
+    Class, Detection, Label,
+};
+use measurements::{Angle, Length};
+
+fn load_ground_truth() -> Vec<Label> {
+    Label::vec_from_path("tests/object_label.txt").unwrap()
+}
+
+/// Repeats the frame so that the devkit samples the full
+/// precision-recall curve, which requires at least 41 ground truth
+/// boxes.
+fn repeat_frame(gt: &[Label], det: &[Detection]) -> Vec<(Vec<Label>, Vec<Detection>)> {
+    vec![(gt.to_vec(), det.to_vec()); 41]
+}
+
+fn to_detection(label: &Label, score: f64) -> Detection {
+    Detection {
+        label: Label {
+            truncation: None,
+            occlusion: None,
+            ..label.clone()
+        },
+        score,
+    }
+}
+
+#[test]
+fn evaluate_perfect_detections() {
+    let gt = load_ground_truth();
+    let det: Vec<Detection> = gt.iter().map(|label| to_detection(label, 0.9)).collect();
+    let result = evaluate(repeat_frame(&gt, &det));
+    assert_eq!(result.classes.len(), 3);
+
+    // The car box is only 27 pixels high and counts as moderate.
+    let car = result.class(&Class::Car).unwrap();
+    for metric in Metric::ALL {
+        let curves = car.metric(metric);
+        assert_eq!(curves.ap40(Difficulty::Easy), 0.0);
+        assert!((curves.ap40(Difficulty::Moderate) - 100.0).abs() < 1e-9);
+        assert!((curves.ap11(Difficulty::Hard) - 100.0).abs() < 1e-9);
+    }
+    let aos = car.aos.as_ref().unwrap();
+    assert!((aos.ap40(Difficulty::Moderate) - 100.0).abs() < 1e-9);
+
+    // The pedestrian is largely occluded and counts as hard.
+    let pedestrian = result.class(&Class::Pedestrian).unwrap();
+    assert_eq!(pedestrian.bbox.ap40(Difficulty::Moderate), 0.0);
+    assert!((pedestrian.box3d.ap40(Difficulty::Hard) - 100.0).abs() < 1e-9);
+
+    let cyclist = result.class(&Class::Cyclist).unwrap();
+    assert!((cyclist.bev.ap40(Difficulty::Easy) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn evaluate_label_score_pairs() {
+    let gt = load_ground_truth();
+    let det: Vec<Detection> = gt.iter().map(|label| to_detection(label, 0.9)).collect();
+    let pairs: Vec<(Label, f64)> = det
+        .iter()
+        .map(|det| (det.label.clone(), det.score))
+        .collect();
+
+    let result = evaluate(vec![(gt.clone(), pairs); 41]);
+    assert_eq!(result, evaluate(repeat_frame(&gt, &det)));
+}
+
+#[test]
+fn evaluate_misses_and_false_positives() {
+    let gt = load_ground_truth();
+    let car = &gt[0];
+
+    // Moved 1 meter away, the 3D box overlap drops below 0.7 while
+    // the 2D box stays the same.
+    let mut shifted = to_detection(car, 0.9);
+    shifted.label.location.z = Length::from_meters(car.location.z.as_meters() + 1.0);
+
+    // A confident false positive elsewhere in the image.
+    let mut false_positive = to_detection(car, 0.95);
+    false_positive.label.bbox.xmin += 300.0;
+    false_positive.label.bbox.xmax += 300.0;
+    false_positive.label.location.x = Length::from_meters(10.0);
+
+    let det = vec![shifted, false_positive];
+    let result = evaluate(repeat_frame(&gt, &det));
+    let car = result.class(&Class::Car).unwrap();
+
+    // The matched detection comes after the false positive, so the
+    // precision is 0.5 at the full recall.
+    assert!((car.bbox.ap40(Difficulty::Moderate) - 50.0).abs() < 1e-9);
+    assert_eq!(car.box3d.ap40(Difficulty::Moderate), 0.0);
+    assert!(result.class(&Class::Pedestrian).is_none());
+}
+
+#[test]
+fn evaluate_dont_care_and_invalid_alpha() {
+    let mut gt = load_ground_truth();
+    let mut dont_care = gt[0].clone();
+    dont_care.class = Class::DontCare;
+    dont_care.bbox.xmin += 300.0;
+    dont_care.bbox.xmax += 300.0;
+    gt.push(dont_care);
+
+    let mut det = vec![to_detection(&gt[0], 0.5)];
+    let mut in_dont_care = to_detection(&gt[0], 0.9);
+    in_dont_care.label.bbox = gt[3].bbox.clone();
+    det.push(in_dont_care);
+    for det in &mut det {
+        det.label.alpha = Angle::from_radians(-10.0);
+    }
+
+    let result = evaluate(repeat_frame(&gt, &det));
+    let car = result.class(&Class::Car).unwrap();
+
+    // The detection in the DontCare area is not a false positive.
+    assert!((car.bbox.ap40(Difficulty::Moderate) - 100.0).abs() < 1e-9);
+    assert!(car.aos.is_none());
+}
@@ -1,4 +1,4 @@
-use kitti_dataset::object::{BoundingBox, Extents, Label, Location, Occlusion};
+use kitti_dataset::object::{BoundingBox, Class, Extents, Label, Location, Occlusion};
 use measurements::{Angle, Length};
 
 #[test]
@@ -6,7 +6,7 @@ fn parse_object_label() {
     let label1: Vec<Label> = Label::vec_from_path("tests/object_label.txt").unwrap();
     let expect = vec![
         Label {
-            class: "Car".to_string(),
+            class: Class::Car,
             truncation: Some(0.0.try_into().unwrap()),
             occlusion: Some(Occlusion::FullyVisible),
             alpha: Angle::from_radians(-1.58),
@@ -29,7 +29,7 @@ fn parse_object_label() {
             rotation_y: Angle::from_radians(-1.59),
         },
         Label {
-            class: "Cyclist".to_string(),
+            class: Class::Cyclist,
             truncation: Some(0.0.try_into().unwrap()),
             occlusion: Some(Occlusion::FullyVisible),
             alpha: Angle::from_radians(-2.46),
@@ -52,7 +52,7 @@ fn parse_object_label() {
             rotation_y: Angle::from_radians(-2.35),
         },
         Label {
-            class: "Pedestrian".to_string(),
+            class: Class::Pedestrian,
             truncation: Some(0.0.try_into().unwrap()),
             occlusion: Some(Occlusion::LargelyOccluded),
             alpha: Angle::from_radians(0.21),
@@ -81,3 +81,23 @@ fn parse_object_label() {
     let label2 = Label::vec_from_str(&text).unwrap();
     assert_eq!(label1, label2);
 }
+
+#[test]
+fn parse_class() {
+    assert_eq!(Class::from_name("Person_sitting"), Class::PersonSitting);
+    assert_eq!(Class::PersonSitting.to_string(), "Person_sitting");
+    assert_eq!(Class::from_name("Bus"), Class::Other("Bus".to_string()));
+    assert!("Cyclist".parse::<Class>().is_ok());
+    assert_eq!("Person".parse::<Class>().unwrap(), Class::Person);
+    assert_eq!(Class::Person.to_string(), "Person");
+    assert!(matches!(
+        "car".parse::<Class>(),
+        Err(kitti_dataset::Error::InvalidClassName(name)) if name == "car"
+    ));
+
+    let text =
+        "Bus 0.00 0 -1.58 587.01 173.33 614.12 200.12 1.65 1.67 3.64 -0.65 1.71 46.70 -1.59\n";
+    let labels = Label::vec_from_str(text).unwrap();
+    assert_eq!(labels[0].class, Class::Other("Bus".to_string()));
+    assert!(Label::write_to_string(&labels).unwrap().starts_with("Bus "));
+}
@@ -0,0 +1,124 @@
+use kitti_dataset::{
+    odometry::{
+        eval::{evaluate, evaluate_sequence},
+        Pose,
+    },
+    Error, ProjectionMatrix,
+};
+
+/// Creates a straight trajectory moving 1 meter per frame along the
+/// z-axis while turning around the y-axis.
+fn straight_trajectory(num_frames: usize, scale: f32, yaw_rate: f32) -> Vec<Pose> {
+    (0..num_frames)
+        .map(|idx| {
+            let (sin, cos) = (yaw_rate * idx as f32).sin_cos();
+            Pose(ProjectionMatrix([
+                [cos, 0.0, sin, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [-sin, 0.0, cos, idx as f32 * scale],
+            ]))
+        })
+        .collect()
+}
+
+#[test]
+fn pose_inverse_and_compose() {
+    let poses = Pose::vec_from_path("tests/pose.txt").unwrap();
+    let pose = &poses[100];
+    let identity = pose.compose(&pose.inverse());
+
+    for (row, expect) in identity.0 .0.iter().zip(Pose::identity().0 .0) {
+        for (value, expect) in row.iter().zip(expect) {
+            assert!((value - expect).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn evaluate_exact_poses() {
+    let poses = Pose::vec_from_path("tests/pose.txt").unwrap();
+    let result = evaluate_sequence(&poses, &poses).unwrap();
+    let drift = result.drift().unwrap();
+
+    assert!(drift.num_segments > 0);
+    assert!(drift.translation.abs() < 1e-9);
+    assert!(drift.rotation.abs() < 1e-3);
+    assert_eq!(result.ate, 0.0);
+    assert_eq!(result.rpe_translation, 0.0);
+}
+
+#[test]
+fn evaluate_scale_drift() {
+    let gt = straight_trajectory(1000, 1.0, 0.0);
+    let est = straight_trajectory(1000, 1.01, 0.0);
+    let result = evaluate([(&gt, &est)]).unwrap();
+
+    // The segment of n meters ends at the frame n + 1 meters away.
+    let total = result.drift().unwrap();
+    assert!((total.translation - 1.0).abs() < 0.02);
+    assert!(total.rotation.abs() < 1e-6);
+
+    let per_length = result.drift_per_length();
+    assert_eq!(per_length.len(), 8);
+    for (length, drift) in per_length {
+        let drift = drift.unwrap();
+        let expect = 1.0 * (length + 1.0) / length;
+        assert!((drift.translation - expect).abs() < 1e-3);
+    }
+
+    // The speed is about 10 m/s.
+    let per_speed = result.drift_per_speed();
+    let (_, drift) = per_speed.iter().find(|(speed, _)| *speed == 10.0).unwrap();
+    assert_eq!(drift.as_ref().unwrap().num_segments, total.num_segments);
+    let (_, drift) = per_speed.iter().find(|(speed, _)| *speed == 20.0).unwrap();
+    assert!(drift.is_none());
+}
+
+#[test]
+fn evaluate_rotation_drift() {
+    let yaw_rate = 1e-4;
+    let gt = straight_trajectory(1000, 1.0, 0.0);
+    let est = straight_trajectory(1000, 1.0, yaw_rate);
+    let result = evaluate_sequence(&gt, &est).unwrap();
+
+    for segment in &result.segments {
+        let expect = yaw_rate as f64 * (segment.length + 1.0) / segment.length;
+        assert!((segment.rotation_error - expect).abs() < 1e-6);
+    }
+    assert!(result.rpe_rotation > 0.0);
+}
+
+#[test]
+fn evaluate_trajectory_offset() {
+    let gt = straight_trajectory(200, 1.0, 0.0);
+    let est: Vec<Pose> = gt
+        .iter()
+        .map(|pose| {
+            let mut pose = pose.clone();
+            pose.0 .0[0][3] += 1.0;
+            pose
+        })
+        .collect();
+    let result = evaluate_sequence(&gt, &est).unwrap();
+
+    assert!((result.ate - 1.0).abs() < 1e-6);
+    assert!(result.rpe_translation < 1e-6);
+    assert!(result.drift().unwrap().translation < 1e-6);
+}
+
+#[test]
+fn evaluate_mismatched_poses() {
+    let gt = straight_trajectory(20, 1.0, 0.0);
+    let est = straight_trajectory(19, 1.0, 0.0);
+    assert!(matches!(
+        evaluate_sequence(&gt, &est),
+        Err(Error::PoseCountMismatch {
+            expect: 20,
+            found: 19
+        })
+    ));
+
+    // Too short to form a segment
+    let result = evaluate_sequence(&gt, &gt).unwrap();
+    assert!(result.drift().is_none());
+}
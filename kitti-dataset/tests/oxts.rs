@@ -1,7 +1,110 @@
-use kitti_dataset::tracking::Oxts;
+use kitti_dataset::{
+    odometry::Pose,
+    tracking::{Oxts, OxtsFloatFormat, PoseFrame},
+};
+use measurements::Length;
 
 #[test]
 fn parse_oxts() {
     let oxts = Oxts::vec_from_path("tests/oxts.txt").unwrap();
     let _text = Oxts::write_to_string(oxts).unwrap();
 }
+
+#[test]
+fn convert_oxts_to_poses() {
+    let oxts = Oxts::vec_from_path("tests/oxts.txt").unwrap();
+    let mercator = Oxts::poses(&oxts, PoseFrame::FirstPose);
+    let enu = Oxts::poses(&oxts, PoseFrame::LocalEnu);
+    assert_eq!(mercator.len(), oxts.len());
+    assert_eq!(enu.len(), oxts.len());
+    assert!(Oxts::poses(Vec::<Oxts>::new(), PoseFrame::FirstPose).is_empty());
+
+    // The first pose is the identity in the devkit convention.
+    let identity = Pose::identity();
+    for (lhs, rhs) in mercator[0].0 .0.iter().zip(&identity.0 .0) {
+        for (a, b) in lhs.iter().zip(rhs) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    // The first ENU pose sits at the origin and keeps the heading.
+    assert!(enu[0].translation().iter().all(|v| v.abs() < 1e-6));
+    let [[r11, ..], [r21, ..], _] = enu[0].0 .0;
+    let yaw = oxts[0].rotation.yaw.as_radians();
+    assert!((f64::from(r21).atan2(f64::from(r11)) - yaw).abs() < 1e-5);
+
+    // Both frames agree on the travelled distance, which matches the
+    // forward speed at 10 Hz.
+    let dist = |poses: &[Pose], idx: usize| {
+        let [x0, y0, z0] = poses[idx].translation();
+        let [x1, y1, z1] = poses[idx + 1].translation();
+        ((x1 - x0).powi(2) + (y1 - y0).powi(2) + (z1 - z0).powi(2)).sqrt() as f64
+    };
+    for (idx, packet) in oxts[..oxts.len() - 1].iter().enumerate() {
+        let mercator_dist = dist(&mercator, idx);
+        let enu_dist = dist(&enu, idx);
+        assert!((mercator_dist - enu_dist).abs() < 0.01);
+
+        let speed = packet.velocity.vf.as_meters_per_second();
+        assert!((mercator_dist - speed * 0.1).abs() < 0.2);
+    }
+}
+
+#[test]
+fn write_oxts_byte_exact() {
+    let text = std::fs::read_to_string("tests/oxts.txt").unwrap();
+    let oxts = Oxts::vec_from_str(&text).unwrap();
+    let written = Oxts::write_to_string(&oxts).unwrap();
+    assert_eq!(written.lines().count(), text.lines().count());
+
+    // Packet 96 is written with `%.14f` in the original file, while
+    // others are reproduced byte-for-byte.
+    let mismatches: Vec<_> = written
+        .lines()
+        .zip(text.lines())
+        .enumerate()
+        .filter(|(_, (lhs, rhs))| lhs != rhs)
+        .map(|(idx, _)| idx)
+        .collect();
+    assert_eq!(mismatches, [96]);
+
+    // The shortest format round-trips arbitrary values.
+    let mut modified = oxts.clone();
+    modified[0].position.alt = Length::from_meters(112.834_922_790_531_23);
+    let shortest = Oxts::write_to_string_with_format(&modified, OxtsFloatFormat::Shortest).unwrap();
+    let parsed = Oxts::vec_from_str(&shortest).unwrap();
+    assert_eq!(
+        Oxts::write_to_string_with_format(&parsed, OxtsFloatFormat::Shortest).unwrap(),
+        shortest
+    );
+    assert_eq!(
+        parsed[0].position.alt.as_meters(),
+        modified[0].position.alt.as_meters()
+    );
+}
+
+#[test]
+fn write_oxts_dir() {
+    let text = std::fs::read_to_string("tests/oxts.txt").unwrap();
+    let oxts = Oxts::vec_from_str(&text).unwrap();
+
+    let dir = std::env::temp_dir().join("kitti-dataset-oxts-dir");
+    Oxts::write_to_dir(&dir, &oxts).unwrap();
+
+    let first = std::fs::read_to_string(dir.join("0000000000.txt")).unwrap();
+    assert_eq!(first, text.lines().next().unwrap().to_string() + "\n");
+
+    let map = Oxts::map_from_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(map.len(), oxts.len());
+    assert_eq!(
+        map.keys().copied().collect::<Vec<_>>(),
+        (0..oxts.len()).collect::<Vec<_>>()
+    );
+    let packets: Vec<_> = map.into_values().collect();
+    assert_eq!(
+        Oxts::write_to_string(packets).unwrap(),
+        Oxts::write_to_string(&oxts).unwrap()
+    );
+}
@@ -0,0 +1,73 @@
+use kitti_dataset::{
+    common::{Point, PointCloud, PointCloudView},
+    Error,
+};
+
+fn sample_point_cloud() -> PointCloud {
+    PointCloud(vec![
+        Point {
+            x: 18.323,
+            y: 0.049,
+            z: 0.829,
+            reflection: 0.0,
+        },
+        Point {
+            x: -5.25,
+            y: 12.5,
+            z: -1.75,
+            reflection: 0.33,
+        },
+        Point {
+            x: f32::MIN_POSITIVE,
+            y: -0.0,
+            z: 1e10,
+            reflection: 1.0,
+        },
+    ])
+}
+
+#[test]
+fn point_cloud_round_trip() {
+    let pcd = sample_point_cloud();
+
+    let bytes = pcd.to_bytes();
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(&bytes[16..20], &(-5.25f32).to_le_bytes());
+    assert_eq!(PointCloud::from_bytes(&bytes).unwrap(), pcd);
+
+    let path = std::env::temp_dir().join("kitti_dataset_point_cloud_round_trip.bin");
+    pcd.write_to_path(&path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    assert_eq!(PointCloud::from_path(&path).unwrap(), pcd);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn truncated_point_cloud() {
+    let mut bytes = sample_point_cloud().to_bytes();
+    bytes.truncate(40);
+    let err = PointCloud::from_bytes(&bytes).unwrap_err();
+    assert!(matches!(err, Error::TruncatedPointCloud(40)));
+}
+
+#[test]
+fn point_cloud_view() {
+    let pcd = sample_point_cloud();
+    let path = std::env::temp_dir().join("kitti_dataset_point_cloud_view.bin");
+    pcd.write_to_path(&path).unwrap();
+
+    let view = PointCloudView::open(&path).unwrap();
+    assert_eq!(view.points(), &pcd.0[..]);
+    assert_eq!(view.as_xyzr()[1], [-5.25, 12.5, -1.75, 0.33]);
+    assert_eq!(view.to_point_cloud(), pcd);
+    drop(view);
+
+    let bytes = pcd.to_bytes();
+    std::fs::write(&path, &bytes[..20]).unwrap();
+    let err = PointCloudView::open(&path).unwrap_err();
+    assert!(matches!(err, Error::TruncatedPointCloud(20)));
+
+    std::fs::write(&path, []).unwrap();
+    assert!(PointCloudView::open(&path).unwrap().is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -1,6 +1,43 @@
-use kitti_dataset::odometry::Pose;
+use kitti_dataset::{odometry::Pose, Error};
 
 #[test]
 fn parse_pose() {
     let _ = Pose::vec_from_path("tests/pose.txt").unwrap();
 }
+
+#[test]
+fn parse_pose_with_comments() {
+    let text = "\
+# sequence 00
+1 0 0 0 0 1 0 0 0 0 1 0
+1 0 0 0.5 0 1 0 0 0 0 1 1.5
+
+";
+    let poses = Pose::vec_from_str(text).unwrap();
+    assert_eq!(poses.len(), 2);
+    assert_eq!(poses[1].0 .0[2][3], 1.5);
+}
+
+#[test]
+fn invalid_pose() {
+    let text = "\
+1 0 0 0 0 1 0 0 0 0 1 0
+1 0 0 0 0 1 0 0 0 0 1
+";
+    let err = Pose::vec_from_str(text).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidPoseLength {
+            line: 2,
+            expect: 12,
+            found: 11
+        }
+    ));
+
+    let text = "1 0 0 0 0 1 0 0 0 0 1 nan0\n";
+    let err = Pose::vec_from_str(text).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidPoseToken { line: 1, token } if token == "nan0"
+    ));
+}
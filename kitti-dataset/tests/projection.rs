@@ -0,0 +1,93 @@
+use kitti_dataset::{
+    common::{Camera, Point, Visibility},
+    object::{self, Label},
+    odometry, tracking,
+};
+
+#[test]
+fn project_label_corners() {
+    let calib = object::Calibration::from_path("tests/object_calib.txt").unwrap();
+    let labels = Label::vec_from_path("tests/object_label.txt").unwrap();
+    let projector = calib
+        .projector(Camera::LeftColor)
+        .with_image_size(1242, 375);
+
+    for label in &labels {
+        let points = projector.project_corners(&label.corners());
+        assert!(points.iter().all(|point| point.is_visible()));
+
+        // The projected box roughly covers the annotated 2D box.
+        let umin = points.iter().map(|p| p.u).fold(f64::INFINITY, f64::min);
+        let umax = points.iter().map(|p| p.u).fold(f64::NEG_INFINITY, f64::max);
+        let center = (umin + umax) / 2.0;
+        let bbox_center = (label.bbox.xmin + label.bbox.xmax) / 2.0;
+        assert!((center - bbox_center).abs() < 10.0);
+
+        // The depth of the bottom center matches the label location.
+        let bottom = projector.rect_to_image([
+            label.location.x.as_meters(),
+            label.location.y.as_meters(),
+            label.location.z.as_meters(),
+        ]);
+        assert!((bottom.depth - label.location.z.as_meters()).abs() < 0.01);
+    }
+}
+
+#[test]
+fn project_point_cloud() {
+    let calib = object::Calibration::from_path("tests/object_calib.txt").unwrap();
+    let projector = calib
+        .projector(Camera::LeftColor)
+        .with_image_size(1242, 375);
+
+    let points = [
+        // In front of the vehicle
+        Point {
+            x: 20.0,
+            y: 0.0,
+            z: 0.0,
+            reflection: 0.5,
+        },
+        // Far to the left
+        Point {
+            x: 5.0,
+            y: 30.0,
+            z: 0.0,
+            reflection: 0.5,
+        },
+        // Behind the vehicle
+        Point {
+            x: -10.0,
+            y: 0.0,
+            z: 0.0,
+            reflection: 0.5,
+        },
+    ];
+    let projected = projector.project_point_cloud(&points);
+
+    assert_eq!(projected[0].visibility, Visibility::InImage);
+    assert!((projected[0].depth - 20.0).abs() < 0.5);
+    assert!((projected[0].u - 621.0).abs() < 50.0);
+    assert_eq!(projected[1].visibility, Visibility::OutsideImage);
+    assert!(projected[1].u < 0.0);
+    assert_eq!(projected[2].visibility, Visibility::BehindCamera);
+
+    let rect = projector.velo_to_rect([20.0, 0.0, 0.0]);
+    assert_eq!(projector.rect_to_image(rect), projected[0]);
+}
+
+#[test]
+fn projector_from_other_calibrations() {
+    let point = [20.0, 0.0, 0.0];
+
+    let calib = tracking::Calibration::from_path("tests/tracking_calib.txt").unwrap();
+    let left = calib.projector(Camera::LeftGray).velo_to_image(point);
+    let right = calib.projector(Camera::RightGray).velo_to_image(point);
+    assert_eq!(left.visibility, Visibility::InImage);
+    assert!(right.u < left.u);
+
+    let calib = odometry::Calibration::from_path("tests/odometry_calib.txt").unwrap();
+    let projected = calib.projector(Camera::LeftGray).velo_to_image(point);
+    assert_eq!(projected.visibility, Visibility::InImage);
+    assert!((projected.depth - 20.0).abs() < 0.5);
+}
@@ -0,0 +1,91 @@
+use kitti_dataset::{dataset::ObjectDataset, object::Split, Error};
+use std::fs;
+
+#[test]
+fn parse_split() {
+    let split: Split = "000003\n000000\n\n000007\n000003\n".parse().unwrap();
+    assert_eq!(split.ids(), [0, 3, 7]);
+    assert!(split.contains(3));
+    assert!(!split.contains(4));
+    assert_eq!(split.write_to_string().unwrap(), "000000\n000003\n000007\n");
+
+    let err = "000001\nabc\n".parse::<Split>().unwrap_err();
+    assert!(matches!(err, Error::InvalidSplitId { line: 2, .. }));
+}
+
+#[test]
+fn random_split() {
+    let (train, val) = Split::random(0..7481, 0.5, 42);
+    assert_eq!(train.len(), 3741);
+    assert_eq!(val.len(), 3740);
+    assert!(train.ids().iter().all(|&id| !val.contains(id)));
+
+    // The split is determined by the seed.
+    assert_eq!(Split::random(0..7481, 0.5, 42), (train.clone(), val));
+    assert_ne!(Split::random(0..7481, 0.5, 43).0, train);
+
+    let (all, none) = Split::random(0..10, 1.0, 0);
+    assert_eq!(all.len(), 10);
+    assert!(none.is_empty());
+}
+
+#[test]
+fn stratified_split() {
+    let items = (0..100).map(|id| {
+        let class = if id < 80 { "Car" } else { "Pedestrian" };
+        (id, class)
+    });
+    let (train, val) = Split::stratified(items, 0.75, 7);
+
+    assert_eq!(train.len(), 75);
+    assert_eq!(val.len(), 25);
+    assert_eq!(train.ids().iter().filter(|&&id| id >= 80).count(), 15);
+    assert_eq!(val.ids().iter().filter(|&&id| id >= 80).count(), 5);
+}
+
+#[test]
+fn stratified_split_duplicate_ids() {
+    // Every ID is listed under both strata, and the first one wins.
+    let items = (0..10)
+        .map(|id| (id, "Car"))
+        .chain((0..10).map(|id| (id, "Pedestrian")))
+        .chain((10..14).map(|id| (id, "Pedestrian")));
+    let (train, val) = Split::stratified(items, 0.5, 7);
+
+    assert_eq!(train.len(), 7);
+    assert_eq!(val.len(), 7);
+    assert!(train.ids().iter().all(|&id| !val.contains(id)));
+    assert_eq!(train.ids().iter().filter(|&&id| id >= 10).count(), 2);
+}
+
+#[test]
+fn object_dataset_subset() {
+    let dir = std::env::temp_dir().join("kitti-dataset-split");
+    let _ = fs::remove_dir_all(&dir);
+    let image_dir = dir.join("training").join("image_2");
+    fs::create_dir_all(&image_dir).unwrap();
+    for id in 0..6 {
+        fs::write(image_dir.join(format!("{id:06}.png")), b"").unwrap();
+    }
+
+    let sets_dir = dir.join("ImageSets");
+    fs::create_dir_all(&sets_dir).unwrap();
+    Split::new([1, 4, 9])
+        .write_to_path(sets_dir.join("train.txt"))
+        .unwrap();
+    Split::new([0, 2])
+        .write_to_path(sets_dir.join("val.txt"))
+        .unwrap();
+
+    let splits = Split::map_from_dir(&sets_dir).unwrap();
+    assert_eq!(splits.keys().collect::<Vec<_>>(), ["train", "val"]);
+
+    let dataset = ObjectDataset::open(dir.join("training")).unwrap();
+    let train = dataset.subset(&splits["train"]);
+    let ids: Vec<_> = train.frame_iter().map(|frame| frame.id()).collect();
+    assert_eq!(ids, [1, 4]);
+    assert_eq!(train.num_frames(), 2);
+    assert_eq!(dataset.subset(&splits["val"]).frame_ids(), [0, 2]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
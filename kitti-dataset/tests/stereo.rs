@@ -0,0 +1,170 @@
+use image::{DynamicImage, GrayImage, ImageBuffer, Luma, Rgb, RgbImage};
+use kitti_dataset::{
+    dataset::{
+        stereo::{DataKind, SampleData, TimeStep},
+        StereoDataset,
+    },
+    stereo::{DisparityMap, FlowField},
+    Error,
+};
+use std::fs;
+
+#[test]
+fn disparity_encoding() {
+    let image: ImageBuffer<Luma<u16>, Vec<u16>> =
+        ImageBuffer::from_raw(3, 2, vec![0, 256, 1000, 65535, 1, 0]).unwrap();
+    let disp = DisparityMap::from_image(&DynamicImage::ImageLuma16(image.clone())).unwrap();
+
+    assert_eq!((disp.width(), disp.height()), (3, 2));
+    assert_eq!(disp.num_valid(), 4);
+    assert_eq!(disp.get(0, 0), None);
+    assert_eq!(disp.get(1, 0), Some(1.0));
+    assert_eq!(disp.get(2, 0), Some(1000.0 / 256.0));
+    assert_eq!(disp.get(1, 1), Some(1.0 / 256.0));
+    assert_eq!(disp.get(3, 0), None);
+    assert_eq!(disp.to_image(), image);
+
+    // Tiny disparities stay valid and large ones are clamped.
+    let mut disp = DisparityMap::new(2, 1);
+    disp.set(0, 0, Some(0.0001));
+    disp.set(1, 0, Some(1000.0));
+    assert_eq!(disp.to_image().into_raw(), [1, 65535]);
+
+    // Negative and non-finite disparities are invalid.
+    let mut disp = DisparityMap::new(4, 1);
+    disp.set(0, 0, Some(-1.0));
+    disp.set(1, 0, Some(f32::NAN));
+    disp.set(2, 0, Some(f32::INFINITY));
+    disp.set(3, 0, Some(0.0));
+    assert_eq!(disp.num_valid(), 1);
+    assert_eq!(disp.get(0, 0), None);
+    assert_eq!(disp.to_image().into_raw(), [0, 0, 0, 1]);
+
+    let gray = DynamicImage::ImageLuma8(GrayImage::new(2, 2));
+    assert!(matches!(
+        DisparityMap::from_image(&gray),
+        Err(Error::InvalidImageFormat(_))
+    ));
+}
+
+#[test]
+fn disparity_png_round_trip() {
+    let mut disp = DisparityMap::new(4, 3);
+    disp.set(0, 0, Some(12.5));
+    disp.set(3, 2, Some(0.25));
+
+    let path = std::env::temp_dir().join("kitti-dataset-disparity.png");
+    disp.write_to_path(&path).unwrap();
+    let loaded = DisparityMap::from_path(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, disp);
+}
+
+#[test]
+fn flow_encoding() {
+    let image: ImageBuffer<Rgb<u16>, Vec<u16>> =
+        ImageBuffer::from_raw(2, 1, vec![32768 + 64, 32768 - 32, 1, 12345, 54321, 0]).unwrap();
+    let flow = FlowField::from_image(&DynamicImage::ImageRgb16(image.clone())).unwrap();
+
+    assert_eq!(flow.num_valid(), 1);
+    assert_eq!(flow.get(0, 0), Some([1.0, -0.5]));
+    assert_eq!(flow.get(1, 0), None);
+    assert_eq!(flow.valid_mask(), [true, false]);
+
+    // Invalid pixels are written as zeros.
+    assert_eq!(
+        flow.to_image().into_raw(),
+        [32768 + 64, 32768 - 32, 1, 0, 0, 0]
+    );
+
+    let mut flow = FlowField::new(1, 1);
+    flow.set(0, 0, Some([-1000.0, 1000.0]));
+    assert_eq!(flow.to_image().into_raw(), [0, 65535, 1]);
+
+    // Non-finite flows are invalid.
+    let mut flow = FlowField::new(3, 1);
+    flow.set(0, 0, Some([f32::NAN, 0.0]));
+    flow.set(1, 0, Some([0.0, f32::INFINITY]));
+    flow.set(2, 0, Some([f32::NEG_INFINITY, 1.0]));
+    assert_eq!(flow.num_valid(), 0);
+    assert_eq!(flow.get(0, 0), None);
+    assert_eq!(flow.to_image().into_raw(), [0; 9]);
+
+    let disp = DynamicImage::ImageLuma16(ImageBuffer::new(2, 2));
+    assert!(matches!(
+        FlowField::from_image(&disp),
+        Err(Error::InvalidImageFormat(_))
+    ));
+}
+
+#[test]
+fn open_stereo_dataset() {
+    let dir = std::env::temp_dir().join("kitti-dataset-stereo");
+    let _ = fs::remove_dir_all(&dir);
+    for key in [
+        "image_2",
+        "disp_noc_0",
+        "flow_occ",
+        "obj_map",
+        "calib_cam_to_cam",
+    ] {
+        fs::create_dir_all(dir.join(key)).unwrap();
+    }
+
+    for id in 0..2 {
+        for suffix in [10, 11] {
+            RgbImage::new(4, 3)
+                .save(dir.join("image_2").join(format!("{id:06}_{suffix}.png")))
+                .unwrap();
+        }
+        let mut disp = DisparityMap::new(4, 3);
+        disp.set(1, 1, Some(id as f32 + 1.0));
+        disp.write_to_path(dir.join("disp_noc_0").join(format!("{id:06}_10.png")))
+            .unwrap();
+        let mut flow = FlowField::new(4, 3);
+        flow.set(2, 1, Some([id as f32, -0.25]));
+        flow.write_to_path(dir.join("flow_occ").join(format!("{id:06}_10.png")))
+            .unwrap();
+        GrayImage::new(4, 3)
+            .save(dir.join("obj_map").join(format!("{id:06}_10.png")))
+            .unwrap();
+    }
+    fs::write(dir.join("image_2").join("notes.txt"), b"").unwrap();
+
+    let dataset = StereoDataset::open(&dir).unwrap();
+    assert_eq!(dataset.frame_ids(), [0, 1]);
+    let keys: Vec<_> = dataset.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            ("disp_noc_0", DataKind::Disparity),
+            ("flow_occ", DataKind::Flow),
+            ("image_2", DataKind::Image),
+            ("obj_map", DataKind::ObjectMap),
+        ]
+    );
+    assert_eq!(
+        dataset.key_index("image_2").unwrap().stray_files(),
+        [dir.join("image_2").join("notes.txt")]
+    );
+
+    let frame = dataset.frame_by_id(1).unwrap();
+    assert_eq!(frame.sample_iter().count(), 4);
+    let next = frame.key_at("image_2", TimeStep::Next).unwrap();
+    assert_eq!(next.path(), dir.join("image_2").join("000001_11.png"));
+    assert!(frame.key_at("disp_noc_0", TimeStep::Next).is_none());
+
+    let SampleData::Disparity(disp) = frame.key("disp_noc_0").unwrap().data().unwrap() else {
+        panic!("expect a disparity map");
+    };
+    assert_eq!(disp.get(1, 1), Some(2.0));
+
+    let SampleData::Flow(flow) = frame.key("flow_occ").unwrap().data().unwrap() else {
+        panic!("expect a flow field");
+    };
+    assert_eq!(flow.get(2, 1), Some([1.0, -0.25]));
+    assert_eq!(flow.num_valid(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,44 @@
+use kitti_dataset::common::{TimeOffset, Timestamp};
+use std::{fs, time::Duration};
+
+#[test]
+fn parse_timestamps() {
+    let text = fs::read_to_string("tests/timestamps.txt").unwrap();
+    let timestamps = Timestamp::vec_from_str(&text).unwrap();
+    assert_eq!(timestamps.len(), 8);
+
+    let elapsed = timestamps[1].duration_since(&timestamps[0]).unwrap();
+    assert_eq!(elapsed, Duration::from_nanos(103_710_530));
+    assert_eq!(timestamps[0].duration_since(&timestamps[1]), None);
+
+    let output = Timestamp::write_to_string(&timestamps).unwrap();
+    assert_eq!(output, text);
+}
+
+#[test]
+fn parse_time_offsets() {
+    let text = fs::read_to_string("tests/times.txt").unwrap();
+    let times = TimeOffset::vec_from_str(&text).unwrap();
+    assert_eq!(times.len(), 12);
+    assert_eq!(times[1].0, Duration::from_nanos(103_622_400));
+
+    let output = TimeOffset::write_to_string(&times).unwrap();
+    assert_eq!(output, text);
+}
+
+#[test]
+fn nearest_timestamp() {
+    let timestamps = Timestamp::vec_from_path("tests/timestamps.txt").unwrap();
+    let target: Timestamp = "2011-09-26 13:02:26.130000000".parse().unwrap();
+    assert_eq!(target.nearest_index(&timestamps), Some(2));
+
+    let times = TimeOffset::vec_from_path("tests/times.txt").unwrap();
+    let target: TimeOffset = "5.0".parse().unwrap();
+    assert_eq!(target.nearest_index(&times), Some(11));
+}
+
+#[test]
+fn invalid_timestamp() {
+    assert!("2011-09-26 13:02".parse::<Timestamp>().is_err());
+    assert!("-1.0".parse::<TimeOffset>().is_err());
+}
@@ -0,0 +1,46 @@
+use kitti_dataset::tracking::{Class, Label, TrackSet};
+
+#[test]
+fn group_tracking_labels() {
+    let labels = Label::vec_from_path("tests/tracking_label.txt").unwrap();
+    let set = TrackSet::from_path("tests/tracking_label.txt").unwrap();
+    assert_eq!(set.labels(), labels.as_slice());
+
+    // Every label belongs to either one track or the untracked list.
+    let num_tracked: usize = set.track_iter().map(|track| track.num_labels()).sum();
+    let num_untracked = set.untracked().count();
+    assert_eq!(num_tracked + num_untracked, labels.len());
+    assert!(set
+        .untracked()
+        .all(|label| label.track_id.is_none() && label.class == Class::DontCare));
+
+    let track = set.track(0).unwrap();
+    assert_eq!(track.id(), 0);
+    assert_eq!(*track.class(), Class::Car);
+    assert_eq!(track.first_frame(), 0);
+    assert!(track.labels().all(|label| label.track_id == Some(0)));
+    assert!(track
+        .labels()
+        .zip(track.labels().skip(1))
+        .all(|(prev, next)| prev.frame < next.frame));
+    assert!(track.num_labels() <= track.frame_range().count());
+    assert_eq!(track.label_at(0), Some(&labels[0]));
+    assert!(track.label_at(track.last_frame() + 1).is_none());
+    assert!(set.track(u32::MAX).is_none());
+
+    let ids: Vec<u32> = set.track_ids().collect();
+    assert_eq!(ids.len(), set.num_tracks());
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Frames list all labels including DontCare areas.
+    let num_frame_labels: usize = set
+        .frame_numbers()
+        .map(|frame| set.frame(frame).count())
+        .sum();
+    assert_eq!(num_frame_labels, labels.len());
+    assert_eq!(
+        set.frame(0).count(),
+        labels.iter().filter(|label| label.frame == 0).count()
+    );
+    assert_eq!(set.frame(u32::MAX).count(), 0);
+}
@@ -0,0 +1,103 @@
+use kitti_dataset::tracking::{eval::evaluate, Class, Label};
+use std::fmt::Write;
+
+fn push_label(text: &mut String, frame: u32, id: i32, class: &str, bbox: [f64; 4]) {
+    let [x1, y1, x2, y2] = bbox;
+    writeln!(
+        text,
+        "{frame} {id} {class} 0 0 0.0 {x1} {y1} {x2} {y2} 1.5 1.6 4.0 0.0 1.5 20.0 0.0"
+    )
+    .unwrap();
+}
+
+#[test]
+fn evaluate_perfect_tracks() {
+    let gt = Label::vec_from_path("tests/tracking_label.txt").unwrap();
+    let tracker: Vec<Label> = gt
+        .iter()
+        .filter(|label| label.class != Class::DontCare)
+        .cloned()
+        .collect();
+    let result = evaluate([(&gt, &tracker)]);
+
+    let car = result.class(&Class::Car).unwrap();
+    assert!(car.clear.true_positives > 0);
+    assert_eq!(car.clear.false_positives, 0);
+    assert_eq!(car.clear.false_negatives, 0);
+    assert_eq!(car.clear.id_switches, 0);
+    assert_eq!(car.clear.mostly_lost, 0);
+    assert!((car.clear.mota() - 1.0).abs() < 1e-9);
+    assert!((car.clear.motp() - 1.0).abs() < 1e-9);
+    assert!((car.hota.hota() - 1.0).abs() < 1e-9);
+    assert!((car.hota.loc_a() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn evaluate_tracking_errors() {
+    let box0 = [100.0, 100.0, 200.0, 200.0];
+    let box1 = [300.0, 100.0, 400.0, 200.0];
+    let van = [800.0, 100.0, 900.0, 200.0];
+    let dont_care = [1000.0, 100.0, 1200.0, 300.0];
+
+    let mut gt = String::new();
+    let mut tracker = String::new();
+
+    for frame in 0..10 {
+        push_label(&mut gt, frame, 0, "Car", box0);
+        push_label(&mut gt, frame, 1, "Car", box1);
+
+        // The ID of the first track switches at frame 5.
+        let id = if frame < 5 { 5 } else { 6 };
+        push_label(&mut tracker, frame, id, "Car", box0);
+
+        // The second track is lost from frame 3 to 5.
+        if !(3..6).contains(&frame) {
+            push_label(&mut tracker, frame, 7, "Car", box1);
+        }
+    }
+
+    // A Van is not evaluated and the matched car box is ignored.
+    push_label(&mut gt, 0, 2, "Van", van);
+    push_label(&mut tracker, 0, 9, "Car", van);
+
+    // Boxes in DontCare areas and small boxes are ignored.
+    push_label(&mut gt, 0, -1, "DontCare", dont_care);
+    push_label(&mut tracker, 0, 10, "Car", [1050.0, 150.0, 1150.0, 250.0]);
+    push_label(&mut tracker, 0, 11, "Car", [500.0, 100.0, 600.0, 120.0]);
+
+    // A false positive
+    push_label(&mut tracker, 0, 12, "Car", [600.0, 100.0, 700.0, 200.0]);
+
+    let gt = Label::vec_from_str(&gt).unwrap();
+    let tracker = Label::vec_from_str(&tracker).unwrap();
+    let result = evaluate([(&gt, &tracker)]);
+    let car = result.class(&Class::Car).unwrap();
+
+    let clear = &car.clear;
+    assert_eq!(clear.true_positives, 17);
+    assert_eq!(clear.false_negatives, 3);
+    assert_eq!(clear.false_positives, 1);
+    assert_eq!(clear.id_switches, 1);
+    assert_eq!(clear.fragmentations, 1);
+    assert_eq!(clear.mostly_tracked, 1);
+    assert_eq!(clear.partly_tracked, 1);
+    assert_eq!(clear.mostly_lost, 0);
+    assert!((clear.mota() - 0.75).abs() < 1e-9);
+
+    let hota = &car.hota;
+    let det_a = 17.0 / 21.0;
+    let ass_a = (5.0 * 0.5 + 5.0 * 0.5 + 7.0 * 0.7) / 17.0;
+    assert!((hota.det_a() - det_a).abs() < 1e-9);
+    assert!((hota.ass_a() - ass_a).abs() < 1e-9);
+    assert!((hota.hota() - (det_a * ass_a).sqrt()).abs() < 1e-9);
+
+    // Accumulating over two identical sequences keeps the ratios.
+    let result = evaluate([(&gt, &tracker), (&gt, &tracker)]);
+    let car2 = result.class(&Class::Car).unwrap();
+    assert_eq!(car2.clear.true_positives, 34);
+    assert!((car2.clear.mota() - 0.75).abs() < 1e-9);
+    assert!((car2.hota.hota() - hota.hota()).abs() < 1e-9);
+
+    let pedestrian = result.class(&Class::Pedestrian).unwrap();
+    assert_eq!(pedestrian.clear.true_positives, 0);
+}
@@ -1,6 +1,8 @@
-use kitti_dataset::tracking::Label;
+use kitti_dataset::tracking::{Class, Label};
 
 #[test]
 fn parse_tracking_label() {
-    let _: Vec<Label> = Label::vec_from_path("tests/tracking_label.txt").unwrap();
+    let labels: Vec<Label> = Label::vec_from_path("tests/tracking_label.txt").unwrap();
+    assert!(labels.iter().all(|label| label.class.is_known()));
+    assert_eq!(labels[0].class, Class::Car);
 }